@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use tokio::sync::Semaphore;
+
+use crate::db;
+use crate::db::pool::MAX_CONNECTIONS;
+use crate::error::AppError;
+use crate::models::{RecipeBackup, RecipeExport, RecipeInput, RecipeStep};
+
+const BACKUP_VERSION: u32 = 1;
+
+/// How many recipes [`import_all_recipes`] will insert at once. Deliberately
+/// kept below [`MAX_CONNECTIONS`] rather than equal to it, so a bulk import
+/// doesn't itself monopolize the whole pool and starve unrelated queries
+/// (e.g. the UI browsing recipes) that are running at the same time.
+pub const MAX_CONCURRENT_IMPORTS: usize = MAX_CONNECTIONS as usize - 1;
+
+/// Builds a full backup envelope of every recipe, with its ingredients and
+/// tags inlined. Recipes are fetched and assembled one at a time rather than
+/// via one giant join so a large library doesn't need every row in memory
+/// simultaneously.
+pub async fn export_all_recipes(pool: &SqlitePool) -> Result<RecipeBackup, AppError> {
+    let recipes = db::recipes::list_all_recipes(pool).await?;
+
+    let mut exported = Vec::with_capacity(recipes.len());
+    for recipe in recipes {
+        let instructions: Vec<RecipeStep> = serde_json::from_str(&recipe.instructions)
+            .map_err(|e| AppError::Internal(format!("corrupt instructions JSON: {e}")))?;
+        let ingredients = db::recipes::get_recipe_ingredients(pool, &recipe.id).await?;
+        let tags = db::recipes::get_recipe_tags(pool, &recipe.id).await?;
+
+        exported.push(RecipeExport {
+            name: recipe.name,
+            description: recipe.description,
+            servings: recipe.servings,
+            yield_unit: recipe.yield_unit,
+            prep_time: recipe.prep_time,
+            cook_time: recipe.cook_time,
+            instructions,
+            image_path: recipe.image_path,
+            source_url: recipe.source_url,
+            notes: recipe.notes,
+            rating_value: recipe.rating_value,
+            rating_count: recipe.rating_count,
+            difficulty: recipe.difficulty,
+            yield_notes: recipe.yield_notes,
+            ingredients,
+            tags,
+        });
+    }
+
+    Ok(RecipeBackup {
+        version: BACKUP_VERSION,
+        recipes: exported,
+    })
+}
+
+/// Imports every recipe in `backup`, skipping ones whose `source_url` is
+/// already present in the library. Returns the number of recipes actually
+/// imported.
+///
+/// Recipes are imported concurrently, up to [`MAX_CONCURRENT_IMPORTS`] at a
+/// time, via a [`Semaphore`] — a large backup (hundreds of recipes, each a
+/// handful of sequential queries) would otherwise take one connection's
+/// round-trip latency times the recipe count to come back.
+pub async fn import_all_recipes(
+    pool: &SqlitePool,
+    backup: RecipeBackup,
+) -> Result<usize, AppError> {
+    if backup.version != BACKUP_VERSION {
+        return Err(AppError::Validation(format!(
+            "unsupported backup version {} (expected {BACKUP_VERSION})",
+            backup.version
+        )));
+    }
+
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_IMPORTS));
+    let tasks: Vec<_> = backup
+        .recipes
+        .into_iter()
+        .map(|recipe| {
+            let pool = pool.clone();
+            let semaphore = Arc::clone(&semaphore);
+            tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("semaphore is never closed");
+                import_one_recipe(&pool, recipe).await
+            })
+        })
+        .collect();
+
+    let mut imported = 0;
+    for task in tasks {
+        let was_imported = task
+            .await
+            .map_err(|e| AppError::Internal(format!("import task panicked: {e}")))??;
+        if was_imported {
+            imported += 1;
+        }
+    }
+
+    Ok(imported)
+}
+
+/// Imports a single recipe, skipping it (returning `false`) if its
+/// `source_url` is already present in the library.
+async fn import_one_recipe(pool: &SqlitePool, recipe: RecipeExport) -> Result<bool, AppError> {
+    if let Some(source_url) = &recipe.source_url {
+        if db::recipes::recipe_exists_by_source_url(pool, source_url).await? {
+            return Ok(false);
+        }
+    }
+
+    let created = db::recipes::create_recipe(
+        pool,
+        RecipeInput {
+            name: recipe.name,
+            description: recipe.description,
+            servings: recipe.servings,
+            yield_unit: recipe.yield_unit,
+            prep_time: recipe.prep_time,
+            cook_time: recipe.cook_time,
+            instructions: recipe.instructions,
+            image_path: recipe.image_path,
+            source_url: recipe.source_url,
+            notes: recipe.notes,
+            rating_value: recipe.rating_value,
+            rating_count: recipe.rating_count,
+            difficulty: recipe.difficulty,
+            yield_notes: recipe.yield_notes,
+        },
+    )
+    .await?;
+
+    for ingredient in &recipe.ingredients {
+        db::recipes::add_recipe_ingredient(pool, &created.id, ingredient, None).await?;
+    }
+    for tag in &recipe.tags {
+        db::recipes::add_recipe_tag(pool, &created.id, tag).await?;
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+    use crate::db::recipes::create_recipe;
+    use crate::models::RecipeIngredientExport;
+
+    fn sample_recipe_input(name: &str, source_url: Option<&str>) -> RecipeInput {
+        RecipeInput {
+            name: name.to_string(),
+            description: Some("a backup test recipe".to_string()),
+            servings: 4,
+            yield_unit: None,
+            prep_time: Some(5),
+            cook_time: Some(10),
+            instructions: vec!["Mix".into(), "Bake".into()],
+            image_path: None,
+            source_url: source_url.map(|s| s.to_string()),
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trips_recipes_with_ingredients_and_tags() {
+        // The global DB_POOL is shared across the whole test binary (see
+        // `init_db_for_test`), so export/import round-trips within it using
+        // distinctive names instead of a separate source/destination DB.
+        let pool = init_db_for_test().await;
+
+        let recipe = create_recipe(
+            &pool,
+            sample_recipe_input(
+                "Backup Roundtrip Lasagna",
+                Some("https://example.com/backup-roundtrip-lasagna"),
+            ),
+        )
+        .await
+        .unwrap();
+        db::recipes::add_recipe_ingredient(
+            &pool,
+            &recipe.id,
+            &RecipeIngredientExport {
+                name: "backup-roundtrip-pasta".to_string(),
+                quantity: 1.0,
+                unit: "box".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        db::recipes::add_recipe_tag(&pool, &recipe.id, "backup-roundtrip-italian")
+            .await
+            .unwrap();
+
+        let exported = export_all_recipes(&pool).await.unwrap();
+        let bundle = RecipeBackup {
+            version: exported.version,
+            recipes: exported
+                .recipes
+                .into_iter()
+                .filter(|r| r.name == "Backup Roundtrip Lasagna")
+                .collect(),
+        };
+        assert_eq!(bundle.recipes.len(), 1);
+
+        // Re-importing the exact same export is a no-op because the
+        // `source_url` already exists; clear it so the round trip produces
+        // a fresh, distinguishable copy to inspect.
+        let mut reimport = bundle;
+        reimport.recipes[0].source_url =
+            Some("https://example.com/backup-roundtrip-lasagna-copy".to_string());
+        reimport.recipes[0].name = "Backup Roundtrip Lasagna Copy".to_string();
+
+        let imported = import_all_recipes(&pool, reimport).await.unwrap();
+        assert_eq!(imported, 1);
+
+        let copy = db::recipes::list_all_recipes(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|r| r.name == "Backup Roundtrip Lasagna Copy")
+            .unwrap();
+        let ingredients = db::recipes::get_recipe_ingredients(&pool, &copy.id)
+            .await
+            .unwrap();
+        assert_eq!(ingredients.len(), 1);
+        assert_eq!(ingredients[0].name, "backup-roundtrip-pasta");
+        let tags = db::recipes::get_recipe_tags(&pool, &copy.id).await.unwrap();
+        assert_eq!(tags, vec!["backup-roundtrip-italian".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn import_skips_recipes_whose_source_url_already_exists() {
+        let pool = init_db_for_test().await;
+        create_recipe(
+            &pool,
+            sample_recipe_input(
+                "Backup Test Existing",
+                Some("https://example.com/backup-test-existing"),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let backup = RecipeBackup {
+            version: BACKUP_VERSION,
+            recipes: vec![RecipeExport {
+                name: "Backup Test Existing Duplicate".to_string(),
+                description: None,
+                servings: 2,
+                yield_unit: None,
+                prep_time: None,
+                cook_time: None,
+                instructions: vec![],
+                image_path: None,
+                source_url: Some("https://example.com/backup-test-existing".to_string()),
+                notes: None,
+                rating_value: None,
+                rating_count: None,
+                difficulty: None,
+                yield_notes: None,
+                ingredients: vec![],
+                tags: vec![],
+            }],
+        };
+
+        let imported = import_all_recipes(&pool, backup).await.unwrap();
+        assert_eq!(imported, 0);
+    }
+
+    #[tokio::test]
+    async fn import_rejects_unsupported_version() {
+        let pool = init_db_for_test().await;
+        let backup = RecipeBackup {
+            version: 99,
+            recipes: vec![],
+        };
+        let result = import_all_recipes(&pool, backup).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn semaphore_caps_concurrent_bulk_import_tasks() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_IMPORTS));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_observed = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..MAX_CONCURRENT_IMPORTS * 3)
+            .map(|_| {
+                let semaphore = Arc::clone(&semaphore);
+                let in_flight = Arc::clone(&in_flight);
+                let max_observed = Arc::clone(&max_observed);
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire().await.unwrap();
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_observed.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let observed = max_observed.load(Ordering::SeqCst);
+        assert!(observed >= 1);
+        assert!(observed <= MAX_CONCURRENT_IMPORTS);
+    }
+}
@@ -0,0 +1,122 @@
+//! Correlation ids for tying a db-layer log line back to the command that
+//! triggered it.
+//!
+//! A command handler calls [`ensure_correlation_id`] once at its entry
+//! point, then runs its work inside [`with_correlation_id`], which stashes
+//! the id in a [`tokio::task_local`] rather than threading an extra
+//! parameter through every db function signature. Db-layer code that wants
+//! to tag its logs reads it back with [`current`].
+
+use std::future::Future;
+
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// Returns `provided` if given, otherwise a freshly generated id. A command
+/// handler calls this once at its entry point so every operation has a
+/// stable id even when the caller didn't supply one.
+pub fn ensure_correlation_id(provided: Option<String>) -> String {
+    provided.unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Runs `f` with `cid` available to [`current`] for the duration of the
+/// future.
+pub async fn with_correlation_id<F: Future>(cid: String, f: F) -> F::Output {
+    CORRELATION_ID.scope(cid, f).await
+}
+
+/// The correlation id of the command currently in flight, or `"-"` when
+/// called outside a [`with_correlation_id`] scope.
+pub fn current() -> String {
+    CORRELATION_ID
+        .try_with(String::clone)
+        .unwrap_or_else(|_| "-".to_string())
+}
+
+/// The longest id [`child_id`] will produce, so a long-running batch
+/// doesn't grow unbounded ids the deeper it nests.
+const MAX_CHILD_ID_LEN: usize = 128;
+
+/// Derives a stable id for one sub-operation of `parent`, as
+/// `"<parent>.<suffix>"`, so a scripted batch (e.g. importing many URLs
+/// under one parent id) can tag every sub-operation's logs without each
+/// generating its own random id. Deterministic — the same `parent` and
+/// `suffix` always produce the same child id — and truncated to
+/// [`MAX_CHILD_ID_LEN`] characters if the combination would run long.
+pub fn child_id(parent: &str, suffix: &str) -> String {
+    let id = format!("{parent}.{suffix}");
+    if id.len() <= MAX_CHILD_ID_LEN {
+        return id;
+    }
+    let mut boundary = MAX_CHILD_ID_LEN;
+    while !id.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+    id[..boundary].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_correlation_id_keeps_a_provided_id() {
+        assert_eq!(
+            ensure_correlation_id(Some("given-id".to_string())),
+            "given-id"
+        );
+    }
+
+    #[test]
+    fn ensure_correlation_id_generates_one_when_absent() {
+        let id = ensure_correlation_id(None);
+        assert!(!id.is_empty());
+    }
+
+    #[test]
+    fn current_is_a_placeholder_outside_any_scope() {
+        assert_eq!(current(), "-");
+    }
+
+    #[test]
+    fn child_id_is_deterministic() {
+        assert_eq!(
+            child_id("parent-cid", "import-url-1"),
+            child_id("parent-cid", "import-url-1")
+        );
+        assert_eq!(
+            child_id("parent-cid", "import-url-1"),
+            "parent-cid.import-url-1"
+        );
+    }
+
+    #[test]
+    fn child_id_differs_by_suffix() {
+        assert_ne!(
+            child_id("parent-cid", "import-url-1"),
+            child_id("parent-cid", "import-url-2")
+        );
+    }
+
+    #[test]
+    fn child_id_truncates_to_the_length_bound() {
+        let parent = "p".repeat(200);
+        let id = child_id(&parent, "suffix");
+
+        assert_eq!(id.len(), MAX_CHILD_ID_LEN);
+    }
+
+    #[tokio::test]
+    async fn db_layer_code_can_read_the_cid_set_by_the_command_entry_point() {
+        async fn fake_db_call() -> String {
+            current()
+        }
+
+        let seen = with_correlation_id("cid-123".to_string(), fake_db_call()).await;
+
+        assert_eq!(seen, "cid-123");
+    }
+}
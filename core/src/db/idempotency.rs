@@ -0,0 +1,347 @@
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+
+use crate::error::AppError;
+
+/// How long a key keeps deduping repeat submissions before a later call
+/// with the same key is treated as a new, unrelated request rather than a
+/// double-submit of the same one.
+pub const IDEMPOTENCY_WINDOW_HOURS: i64 = 24;
+
+/// Sentinel [`reserve`] writes as `entity_id` to claim a key before the
+/// entity it will point to exists yet. `entity_id` is `NOT NULL`, and no
+/// real [`uuid::Uuid`] is ever empty, so this is unambiguous as "claimed,
+/// but not finalized."
+const PENDING_ENTITY_ID: &str = "";
+
+/// How many times [`reserve`] polls a claim still pending under
+/// [`PENDING_ENTITY_ID`] before giving up — the other caller finishing its
+/// insert and [`record`]-ing a real id is expected to take low
+/// milliseconds, not longer.
+const RESERVE_POLL_ATTEMPTS: u32 = 20;
+const RESERVE_POLL_DELAY: Duration = Duration::from_millis(5);
+
+/// The id recorded against `key` if it was used within
+/// [`IDEMPOTENCY_WINDOW_HOURS`], for a create command to short-circuit a
+/// double-submit instead of inserting a duplicate. `None` if `key` is
+/// unseen or its window has lapsed, in which case the caller should
+/// proceed with creating the entity and [`record`] the result.
+pub async fn lookup(pool: &SqlitePool, key: &str) -> Result<Option<String>, AppError> {
+    let entity_id = sqlx::query_scalar(
+        "SELECT entity_id FROM idempotency_keys \
+         WHERE key = ? AND created_at >= datetime('now', ? || ' hours')",
+    )
+    .bind(key)
+    .bind(-IDEMPOTENCY_WINDOW_HOURS)
+    .fetch_optional(pool)
+    .await?;
+    Ok(entity_id)
+}
+
+/// Records that `key` produced `entity_id`, so a later [`lookup`] within
+/// the window returns it instead of letting the caller create a
+/// duplicate. Overwrites any existing row for `key`, since this is only
+/// ever called either to finalize a [`reserve`] this same caller holds, or
+/// (via [`reserve`] itself) to take over a row whose window already
+/// lapsed.
+pub async fn record(pool: &SqlitePool, key: &str, entity_id: &str) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO idempotency_keys (key, entity_id) VALUES (?, ?) \
+         ON CONFLICT(key) DO UPDATE SET entity_id = excluded.entity_id, \
+         created_at = datetime('now')",
+    )
+    .bind(key)
+    .bind(entity_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Outcome of [`reserve`].
+pub enum Reservation {
+    /// No live claim existed for `key` (or one did, but its window had
+    /// lapsed) — this caller now owns it and should create the entity,
+    /// then [`record`] its real id. Call [`release`] instead if creation
+    /// fails, so the reservation doesn't block a legitimate retry for the
+    /// rest of the window.
+    Claimed,
+    /// `key` was already claimed by a concurrent caller and has resolved
+    /// to a real entity id — fetch and return that row instead of
+    /// creating a duplicate.
+    AlreadyClaimed(String),
+}
+
+/// Atomically claims `key` for a new entity, closing the race a plain
+/// lookup-then-create-then-record sequence leaves open: two concurrent
+/// callers with the same key could both miss [`lookup`] (nothing recorded
+/// yet), both create their own entity, and whichever [`record`] ran last
+/// would silently decide which one the key points to while the other's row
+/// lives on as an orphaned duplicate.
+///
+/// Leans on `idempotency_keys.key` being a `PRIMARY KEY`: this inserts a
+/// [`PENDING_ENTITY_ID`] placeholder as its first write, so only one of two
+/// racing callers can land the row. The loser polls briefly for the winner
+/// to [`record`] a real id rather than creating its own duplicate entity;
+/// if the winner hasn't finished within [`RESERVE_POLL_ATTEMPTS`], this
+/// gives up and returns [`AppError::Conflict`] rather than guessing.
+pub async fn reserve(pool: &SqlitePool, key: &str) -> Result<Reservation, AppError> {
+    let inserted = sqlx::query("INSERT INTO idempotency_keys (key, entity_id) VALUES (?, ?)")
+        .bind(key)
+        .bind(PENDING_ENTITY_ID)
+        .execute(pool)
+        .await;
+
+    match inserted {
+        Ok(_) => Ok(Reservation::Claimed),
+        Err(err) if err.as_database_error().is_some_and(|e| e.is_unique_violation()) => {
+            resolve_existing_claim(pool, key).await
+        }
+        Err(err) => Err(AppError::Database(err)),
+    }
+}
+
+async fn resolve_existing_claim(pool: &SqlitePool, key: &str) -> Result<Reservation, AppError> {
+    for attempt in 0..RESERVE_POLL_ATTEMPTS {
+        match lookup(pool, key).await? {
+            Some(entity_id) if entity_id != PENDING_ENTITY_ID => {
+                return Ok(Reservation::AlreadyClaimed(entity_id));
+            }
+            Some(_) => {
+                if attempt + 1 < RESERVE_POLL_ATTEMPTS {
+                    tokio::time::sleep(RESERVE_POLL_DELAY).await;
+                }
+            }
+            None => {
+                // The existing row's window had lapsed as of this `lookup`,
+                // but another caller retrying the same expired key could be
+                // racing to take it over at the same instant, so this can't
+                // just `record` (a plain upsert) the way a never-claimed key
+                // can -- that would let both callers believe they'd won.
+                // The conditional `UPDATE` below re-checks the window at
+                // write time, so only one caller's takeover actually lands;
+                // the other falls through to poll like the pending case.
+                if take_over_lapsed_claim(pool, key).await? {
+                    return Ok(Reservation::Claimed);
+                }
+                if attempt + 1 < RESERVE_POLL_ATTEMPTS {
+                    tokio::time::sleep(RESERVE_POLL_DELAY).await;
+                }
+            }
+        }
+    }
+    Err(AppError::Conflict(format!(
+        "a request with idempotency key '{key}' is still being processed"
+    )))
+}
+
+/// Atomically takes over a claim whose window has lapsed, the way
+/// [`reserve`]'s initial `INSERT` claims a key that's never been used.
+/// Only succeeds if `key`'s row is still past [`IDEMPOTENCY_WINDOW_HOURS`]
+/// at the moment of the `UPDATE`, so two callers racing to take over the
+/// same expired key can't both come away believing they won.
+async fn take_over_lapsed_claim(pool: &SqlitePool, key: &str) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "UPDATE idempotency_keys SET entity_id = ?, created_at = datetime('now') \
+         WHERE key = ? AND created_at < datetime('now', ? || ' hours')",
+    )
+    .bind(PENDING_ENTITY_ID)
+    .bind(key)
+    .bind(-IDEMPOTENCY_WINDOW_HOURS)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Releases a still-[`PENDING_ENTITY_ID`] reservation made by [`reserve`],
+/// e.g. when the entity it was claiming for failed to create. Only removes
+/// the row while it's still pending, so it can't clobber a real id some
+/// other caller already [`record`]-ed; without this, a failed creation
+/// would leave the key claimed for the rest of the window with nothing to
+/// ever resolve it, blocking a legitimate retry.
+pub async fn release(pool: &SqlitePool, key: &str) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE key = ? AND entity_id = ?")
+        .bind(key)
+        .bind(PENDING_ENTITY_ID)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+
+    #[tokio::test]
+    async fn lookup_of_an_unknown_key_is_none() {
+        let pool = init_db_for_test().await;
+
+        let found = lookup(&pool, "idempotency-test-unknown-key").await.unwrap();
+
+        assert_eq!(found, None);
+    }
+
+    #[tokio::test]
+    async fn record_then_lookup_returns_the_recorded_entity_id() {
+        let pool = init_db_for_test().await;
+
+        record(&pool, "idempotency-test-key", "entity-123")
+            .await
+            .unwrap();
+        let found = lookup(&pool, "idempotency-test-key").await.unwrap();
+
+        assert_eq!(found, Some("entity-123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn recording_the_same_key_again_overwrites_the_entity_id() {
+        let pool = init_db_for_test().await;
+
+        record(&pool, "idempotency-test-overwrite-key", "entity-1")
+            .await
+            .unwrap();
+        record(&pool, "idempotency-test-overwrite-key", "entity-2")
+            .await
+            .unwrap();
+        let found = lookup(&pool, "idempotency-test-overwrite-key")
+            .await
+            .unwrap();
+
+        assert_eq!(found, Some("entity-2".to_string()));
+    }
+
+    #[tokio::test]
+    async fn reserve_of_an_unclaimed_key_is_claimed() {
+        let pool = init_db_for_test().await;
+
+        let reservation = reserve(&pool, "idempotency-test-reserve-key").await.unwrap();
+
+        assert!(matches!(reservation, Reservation::Claimed));
+    }
+
+    #[tokio::test]
+    async fn reserve_of_a_key_already_finalized_returns_its_entity_id() {
+        let pool = init_db_for_test().await;
+        record(&pool, "idempotency-test-finalized-key", "entity-123")
+            .await
+            .unwrap();
+
+        let reservation = reserve(&pool, "idempotency-test-finalized-key")
+            .await
+            .unwrap();
+
+        assert!(matches!(reservation, Reservation::AlreadyClaimed(id) if id == "entity-123"));
+    }
+
+    #[tokio::test]
+    async fn two_concurrent_reserves_of_the_same_key_only_let_one_caller_claim_it() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-racing-key";
+
+        let (first, second) = tokio::join!(reserve(&pool, key), reserve(&pool, key));
+
+        let claimed_count = [&first, &second]
+            .into_iter()
+            .filter(|r| matches!(r, Ok(Reservation::Claimed)))
+            .count();
+        assert_eq!(claimed_count, 1, "exactly one racing caller should claim the key");
+    }
+
+    #[tokio::test]
+    async fn only_one_concurrent_takeover_of_a_lapsed_claim_succeeds() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-lapsed-takeover-race-key";
+        sqlx::query(
+            "INSERT INTO idempotency_keys (key, entity_id, created_at) \
+             VALUES (?, ?, datetime('now', '-25 hours'))",
+        )
+        .bind(key)
+        .bind(PENDING_ENTITY_ID)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let (first, second) = tokio::join!(
+            take_over_lapsed_claim(&pool, key),
+            take_over_lapsed_claim(&pool, key),
+        );
+
+        let won_count = [first.unwrap(), second.unwrap()]
+            .into_iter()
+            .filter(|won| *won)
+            .count();
+        assert_eq!(won_count, 1, "exactly one racing takeover should land");
+    }
+
+    #[tokio::test]
+    async fn reserve_of_a_lapsed_claim_is_claimed_by_only_one_of_two_racing_callers() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-lapsed-reserve-race-key";
+        sqlx::query(
+            "INSERT INTO idempotency_keys (key, entity_id, created_at) \
+             VALUES (?, ?, datetime('now', '-25 hours'))",
+        )
+        .bind(key)
+        .bind("stale-entity-id")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let (first, second) = tokio::join!(reserve(&pool, key), reserve(&pool, key));
+
+        let claimed_count = [&first, &second]
+            .into_iter()
+            .filter(|r| matches!(r, Ok(Reservation::Claimed)))
+            .count();
+        assert_eq!(claimed_count, 1, "exactly one racing caller should reclaim the lapsed key");
+    }
+
+    #[tokio::test]
+    async fn reserve_of_a_stale_pending_claim_past_the_window_is_claimed_again() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-stale-pending-key";
+        sqlx::query(
+            "INSERT INTO idempotency_keys (key, entity_id, created_at) \
+             VALUES (?, ?, datetime('now', '-25 hours'))",
+        )
+        .bind(key)
+        .bind(PENDING_ENTITY_ID)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let reservation = reserve(&pool, key).await.unwrap();
+
+        assert!(matches!(reservation, Reservation::Claimed));
+    }
+
+    #[tokio::test]
+    async fn release_removes_a_pending_reservation_so_it_can_be_reclaimed() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-release-key";
+        assert!(matches!(
+            reserve(&pool, key).await.unwrap(),
+            Reservation::Claimed
+        ));
+
+        release(&pool, key).await.unwrap();
+
+        assert!(matches!(
+            reserve(&pool, key).await.unwrap(),
+            Reservation::Claimed
+        ));
+    }
+
+    #[tokio::test]
+    async fn release_leaves_a_finalized_reservation_alone() {
+        let pool = init_db_for_test().await;
+        let key = "idempotency-test-release-finalized-key";
+        record(&pool, key, "entity-123").await.unwrap();
+
+        release(&pool, key).await.unwrap();
+
+        let found = lookup(&pool, key).await.unwrap();
+        assert_eq!(found, Some("entity-123".to_string()));
+    }
+}
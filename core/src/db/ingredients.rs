@@ -0,0 +1,357 @@
+use sqlx::SqlitePool;
+
+use crate::error::AppError;
+use crate::models::{CategoryGroup, Ingredient};
+
+/// Returns ingredients whose name starts with `prefix` (case-insensitive),
+/// ordered by name, for type-ahead suggestions. An empty prefix returns the
+/// top `limit` ingredients by name.
+pub async fn search_ingredients(
+    pool: &SqlitePool,
+    prefix: &str,
+    limit: i64,
+) -> Result<Vec<Ingredient>, AppError> {
+    let normalized = prefix.trim().to_lowercase();
+    let pattern = format!("{normalized}%");
+
+    let ingredients = sqlx::query_as::<_, Ingredient>(
+        "SELECT * FROM ingredients WHERE lower(name) LIKE ? ORDER BY name LIMIT ?",
+    )
+    .bind(pattern)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ingredients)
+}
+
+/// The default unit for the ingredient matching `name` (case/whitespace
+/// normalized), for pre-filling a recipe form's unit field. Returns `None`
+/// both when the ingredient has no default unit and when no ingredient
+/// matches the name at all.
+pub async fn get_default_unit(pool: &SqlitePool, name: &str) -> Result<Option<String>, AppError> {
+    let normalized = name.trim().to_lowercase();
+
+    let default_unit: Option<String> =
+        sqlx::query_scalar("SELECT default_unit FROM ingredients WHERE lower(name) = ? LIMIT 1")
+            .bind(normalized)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+
+    Ok(default_unit)
+}
+
+/// Every distinct, non-blank category in use across `ingredients` and
+/// `manual_shopping_items`, case/whitespace-normalized and sorted, for a
+/// category picker that only offers choices the user has actually used.
+/// There is no `shopping_list_items` table in this schema — the shopping
+/// list is computed on the fly from meal plans (see
+/// [`crate::db::shopping_list::get_aggregated_shopping_list`]) rather than
+/// persisted, so it contributes no categories of its own here.
+pub async fn get_used_categories(pool: &SqlitePool) -> Result<Vec<String>, AppError> {
+    let categories: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT lower(trim(category)) AS category FROM ( \
+             SELECT category FROM ingredients \
+             UNION ALL \
+             SELECT category FROM manual_shopping_items \
+         ) WHERE category IS NOT NULL AND trim(category) != '' \
+         ORDER BY category",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(categories)
+}
+
+/// Every ingredient, grouped by category for a management screen that
+/// wants category headers rather than a flat alphabetical list. Within a
+/// category, ingredients are ordered by name; categories themselves sort
+/// alphabetically, except uncategorized ingredients (`NULL` or blank
+/// `category`), which are grouped under `"Other"` and always sort last.
+pub async fn get_ingredients_grouped(pool: &SqlitePool) -> Result<Vec<CategoryGroup>, AppError> {
+    let ingredients = sqlx::query_as::<_, Ingredient>(
+        "SELECT * FROM ingredients \
+         ORDER BY \
+             CASE WHEN category IS NULL OR trim(category) = '' THEN 1 ELSE 0 END, \
+             category, \
+             name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut groups: Vec<CategoryGroup> = Vec::new();
+    for ingredient in ingredients {
+        let category = match ingredient.category.as_deref() {
+            Some(category) if !category.trim().is_empty() => category.to_string(),
+            _ => "Other".to_string(),
+        };
+
+        match groups.last_mut() {
+            Some(group) if group.category == category => group.ingredients.push(ingredient),
+            _ => groups.push(CategoryGroup {
+                category,
+                ingredients: vec![ingredient],
+            }),
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Renames a category to `to` everywhere it's used, matched
+/// case-insensitively against `from`, across the same two tables
+/// [`get_used_categories`] draws its list from — `ingredients` and
+/// `manual_shopping_items` — for a "my categories drifted, merge them"
+/// cleanup pass. Returns the total number of rows updated across both
+/// tables.
+pub async fn recategorize(pool: &SqlitePool, from: &str, to: &str) -> Result<u64, AppError> {
+    let from = from.trim().to_lowercase();
+
+    let ingredients_updated =
+        sqlx::query("UPDATE ingredients SET category = ? WHERE lower(trim(category)) = ?")
+            .bind(to)
+            .bind(&from)
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+    let manual_items_updated = sqlx::query(
+        "UPDATE manual_shopping_items SET category = ? WHERE lower(trim(category)) = ?",
+    )
+    .bind(to)
+    .bind(&from)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(ingredients_updated + manual_items_updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+    use uuid::Uuid;
+
+    async fn insert_ingredient(pool: &SqlitePool, name: &str) {
+        sqlx::query("INSERT INTO ingredients (id, name) VALUES (?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(name)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn search_ingredients_matches_prefix_case_insensitively() {
+        let pool = init_db_for_test().await;
+        insert_ingredient(&pool, "autocomplete-test Cherry Tomato").await;
+        insert_ingredient(&pool, "autocomplete-test cherry pie filling").await;
+        insert_ingredient(&pool, "autocomplete-test Basil").await;
+
+        let results = search_ingredients(&pool, "autocomplete-test cherry", 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|i| i.name.to_lowercase().contains("cherry")));
+    }
+
+    #[tokio::test]
+    async fn search_ingredients_respects_limit() {
+        let pool = init_db_for_test().await;
+        for n in 0..5 {
+            insert_ingredient(&pool, &format!("autocomplete-limit-test-{n}")).await;
+        }
+
+        let results = search_ingredients(&pool, "autocomplete-limit-test", 2)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_used_categories_dedupes_case_insensitively_and_sorts() {
+        use crate::db::manual_items::create_manual_item;
+        use crate::models::ManualShoppingItemInput;
+
+        let pool = init_db_for_test().await;
+        sqlx::query("INSERT INTO ingredients (id, name, category) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind("category-test-carrot")
+            .bind("Produce")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO ingredients (id, name, category) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind("category-test-flour")
+            .bind("Pantry")
+            .execute(&pool)
+            .await
+            .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-01-05".to_string(),
+                name: "category-test-lettuce".to_string(),
+                quantity: None,
+                unit: None,
+                category: Some("produce".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let categories = get_used_categories(&pool).await.unwrap();
+
+        assert!(categories.iter().filter(|c| *c == "produce").count() == 1);
+        assert!(categories.contains(&"produce".to_string()));
+        assert!(categories.contains(&"pantry".to_string()));
+        let pantry_index = categories.iter().position(|c| c == "pantry").unwrap();
+        let produce_index = categories.iter().position(|c| c == "produce").unwrap();
+        assert!(pantry_index < produce_index);
+    }
+
+    async fn insert_ingredient_with_category(
+        pool: &SqlitePool,
+        name: &str,
+        category: Option<&str>,
+    ) {
+        sqlx::query("INSERT INTO ingredients (id, name, category) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(name)
+            .bind(category)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_ingredients_grouped_sorts_categories_with_other_last() {
+        let pool = init_db_for_test().await;
+        insert_ingredient_with_category(&pool, "grouped-test Flour", Some("Pantry")).await;
+        insert_ingredient_with_category(&pool, "grouped-test Carrot", Some("Produce")).await;
+        insert_ingredient_with_category(&pool, "grouped-test Apple", Some("Produce")).await;
+        insert_ingredient_with_category(&pool, "grouped-test Mystery", None).await;
+
+        let groups = get_ingredients_grouped(&pool).await.unwrap();
+        let groups: Vec<_> = groups
+            .into_iter()
+            .filter(|g| {
+                g.ingredients
+                    .iter()
+                    .any(|i| i.name.starts_with("grouped-test"))
+            })
+            .collect();
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].category, "Pantry");
+        assert_eq!(groups[1].category, "Produce");
+        assert_eq!(
+            groups[1]
+                .ingredients
+                .iter()
+                .map(|i| &i.name)
+                .collect::<Vec<_>>(),
+            vec!["grouped-test Apple", "grouped-test Carrot"]
+        );
+        assert_eq!(groups[2].category, "Other");
+        assert_eq!(groups[2].ingredients[0].name, "grouped-test Mystery");
+    }
+
+    #[tokio::test]
+    async fn get_default_unit_returns_the_unit_for_a_matching_ingredient() {
+        let pool = init_db_for_test().await;
+        insert_ingredient_with_category_and_unit(&pool, "default-unit-test Flour", Some("cup"))
+            .await;
+
+        let unit = get_default_unit(&pool, "Default-Unit-Test Flour")
+            .await
+            .unwrap();
+
+        assert_eq!(unit, Some("cup".to_string()));
+    }
+
+    #[tokio::test]
+    async fn get_default_unit_returns_none_for_an_ingredient_with_no_default_unit() {
+        let pool = init_db_for_test().await;
+        insert_ingredient_with_category_and_unit(&pool, "default-unit-test Salt", None).await;
+
+        let unit = get_default_unit(&pool, "default-unit-test Salt")
+            .await
+            .unwrap();
+
+        assert_eq!(unit, None);
+    }
+
+    #[tokio::test]
+    async fn get_default_unit_returns_none_for_an_unknown_name() {
+        let pool = init_db_for_test().await;
+
+        let unit = get_default_unit(&pool, "default-unit-test Nonexistent")
+            .await
+            .unwrap();
+
+        assert_eq!(unit, None);
+    }
+
+    async fn insert_ingredient_with_category_and_unit(
+        pool: &SqlitePool,
+        name: &str,
+        default_unit: Option<&str>,
+    ) {
+        sqlx::query("INSERT INTO ingredients (id, name, default_unit) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(name)
+            .bind(default_unit)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn recategorize_renames_a_category_across_ingredients_and_manual_items() {
+        use crate::db::manual_items::create_manual_item;
+        use crate::models::ManualShoppingItemInput;
+
+        let pool = init_db_for_test().await;
+        insert_ingredient_with_category(&pool, "recategorize-test Carrot", Some("Veggies")).await;
+        insert_ingredient_with_category(&pool, "recategorize-test Pasta", Some("Pantry")).await;
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-02-02".to_string(),
+                name: "recategorize-test Lettuce".to_string(),
+                quantity: None,
+                unit: None,
+                category: Some("veggies".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let updated = recategorize(&pool, "Veggies", "Produce").await.unwrap();
+
+        assert_eq!(updated, 2);
+        let categories = get_used_categories(&pool).await.unwrap();
+        assert!(categories.contains(&"produce".to_string()));
+        assert!(!categories.contains(&"veggies".to_string()));
+        assert!(categories.contains(&"pantry".to_string()));
+    }
+
+    #[tokio::test]
+    async fn recategorize_of_an_unused_category_affects_nothing() {
+        let pool = init_db_for_test().await;
+        insert_ingredient_with_category(&pool, "recategorize-noop-test Rice", Some("Pantry"))
+            .await;
+
+        let updated = recategorize(&pool, "Nonexistent", "Produce").await.unwrap();
+
+        assert_eq!(updated, 0);
+    }
+}
@@ -0,0 +1,1299 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{
+    FrequentItem, ListDiff, ListProgress, ManualShoppingItem, ManualShoppingItemInput,
+    ManualShoppingItemUpdate, ShoppingListQuantityDiff, ShoppingListSummary,
+};
+use crate::utils::ingredient_name::singularize_ingredient_name;
+use crate::utils::units::convert_quantity;
+
+/// Combines `new_quantity new_unit` into `existing_quantity existing_unit`
+/// when [`convert_quantity`] can reconcile the two units, returning the
+/// combined quantity. Returns `None` when the units are incompatible (e.g.
+/// `"lb"` vs `"cup"`), in which case the caller should keep the items
+/// separate rather than combine them.
+pub fn aggregate_quantities(
+    existing_quantity: Option<f64>,
+    existing_unit: Option<&str>,
+    new_quantity: Option<f64>,
+    new_unit: Option<&str>,
+) -> Option<f64> {
+    let new_unit = new_unit.unwrap_or("");
+    let existing_unit = existing_unit.unwrap_or("");
+    let converted = convert_quantity(new_quantity.unwrap_or(0.0), new_unit, existing_unit)?;
+    Some(existing_quantity.unwrap_or(0.0) + converted)
+}
+
+/// Every non-deleted manual item on `week_start`'s list, for a week view
+/// summary — checked and unchecked items alike, unlike [`get_list_progress`]
+/// which only counts them.
+pub async fn list_items_for_week(
+    pool: &SqlitePool,
+    week_start: &str,
+) -> Result<Vec<ManualShoppingItem>, AppError> {
+    let items = sqlx::query_as::<_, ManualShoppingItem>(
+        "SELECT * FROM manual_shopping_items WHERE week_start = ? AND deleted_at IS NULL",
+    )
+    .bind(week_start)
+    .fetch_all(pool)
+    .await?;
+    Ok(items)
+}
+
+/// Inserts a manual shopping item. When `merge_duplicates` is true, first
+/// looks for an existing non-checked item in the same week with the same
+/// (case-insensitive) name and a unit [`aggregate_quantities`] can
+/// reconcile with the new one, and bumps its quantity instead of inserting
+/// a duplicate row. Incompatible units always insert a separate row, since
+/// there's no sound way to combine them.
+pub async fn add_shopping_item(
+    pool: &SqlitePool,
+    input: ManualShoppingItemInput,
+    merge_duplicates: bool,
+) -> Result<ManualShoppingItem, AppError> {
+    if merge_duplicates {
+        let normalized_name = input.name.trim().to_lowercase();
+        let candidates = sqlx::query_as::<_, ManualShoppingItem>(
+            "SELECT * FROM manual_shopping_items \
+             WHERE week_start = ? AND is_checked = 0 AND deleted_at IS NULL AND lower(name) = ?",
+        )
+        .bind(&input.week_start)
+        .bind(&normalized_name)
+        .fetch_all(pool)
+        .await?;
+
+        for existing in candidates {
+            if let Some(merged_quantity) = aggregate_quantities(
+                existing.quantity,
+                existing.unit.as_deref(),
+                input.quantity,
+                input.unit.as_deref(),
+            ) {
+                return update_manual_item(
+                    pool,
+                    &existing.id,
+                    ManualShoppingItemUpdate {
+                        quantity: Some(merged_quantity),
+                        ..Default::default()
+                    },
+                )
+                .await;
+            }
+        }
+    }
+
+    create_manual_item(pool, input).await
+}
+
+pub async fn create_manual_item(
+    pool: &SqlitePool,
+    input: ManualShoppingItemInput,
+) -> Result<ManualShoppingItem, AppError> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO manual_shopping_items (id, week_start, name, quantity, unit, category) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&input.week_start)
+    .bind(&input.name)
+    .bind(input.quantity)
+    .bind(&input.unit)
+    .bind(&input.category)
+    .execute(pool)
+    .await?;
+
+    get_manual_item_by_id(pool, &id).await?.ok_or_else(|| {
+        AppError::Internal("manual item vanished immediately after insert".to_string())
+    })
+}
+
+pub async fn get_manual_item_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<ManualShoppingItem>, AppError> {
+    let item =
+        sqlx::query_as::<_, ManualShoppingItem>("SELECT * FROM manual_shopping_items WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(item)
+}
+
+pub async fn update_manual_item(
+    pool: &SqlitePool,
+    id: &str,
+    update: ManualShoppingItemUpdate,
+) -> Result<ManualShoppingItem, AppError> {
+    let existing = get_manual_item_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    let quantity = update.quantity.or(existing.quantity);
+    let unit = update.unit.or(existing.unit);
+    let category = update.category.or(existing.category);
+    let is_checked = update.is_checked.unwrap_or(existing.is_checked);
+
+    sqlx::query(
+        "UPDATE manual_shopping_items SET quantity = ?, unit = ?, category = ?, is_checked = ? \
+         WHERE id = ?",
+    )
+    .bind(quantity)
+    .bind(&unit)
+    .bind(&category)
+    .bind(is_checked)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    get_manual_item_by_id(pool, id).await?.ok_or_else(|| {
+        AppError::Internal("manual item vanished immediately after update".to_string())
+    })
+}
+
+/// Flips `is_checked`, for the common "tap the checkbox" action where the
+/// frontend doesn't want to fetch the item first just to know which way to
+/// flip it. NotFound for an unknown id.
+pub async fn toggle_item_checked(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<ManualShoppingItem, AppError> {
+    get_manual_item_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    sqlx::query("UPDATE manual_shopping_items SET is_checked = NOT is_checked WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    get_manual_item_by_id(pool, id).await?.ok_or_else(|| {
+        AppError::Internal("manual item vanished immediately after toggle".to_string())
+    })
+}
+
+/// Marks a manual shopping item as deleted without removing the row, so a
+/// user can undo the delete via [`restore_shopping_item`] and so
+/// [`crate::db::purge::purge_old_soft_deleted_shopping_items`] has a
+/// `deleted_at` to age out. Idempotent — soft-deleting an already-deleted
+/// item just refreshes its timestamp.
+pub async fn soft_delete_shopping_item(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    get_manual_item_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    sqlx::query("UPDATE manual_shopping_items SET deleted_at = datetime('now') WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Undoes [`soft_delete_shopping_item`], clearing `deleted_at` so the item
+/// shows up again and is no longer a purge candidate.
+pub async fn restore_shopping_item(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    get_manual_item_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    sqlx::query("UPDATE manual_shopping_items SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Permanently removes a single soft-deleted manual item, for a user who
+/// wants one specific trashed item gone now rather than waiting on
+/// [`crate::db::purge::purge_old_soft_deleted_shopping_items`]'s retention
+/// window. Refuses to touch an item that hasn't been soft-deleted, since
+/// that's almost certainly a UI bug rather than an intentional hard delete.
+pub async fn hard_delete_shopping_item(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    let item = get_manual_item_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    if item.deleted_at.is_none() {
+        return Err(AppError::Validation(format!(
+            "manual item '{id}' is not soft-deleted and cannot be permanently removed"
+        )));
+    }
+
+    sqlx::query("DELETE FROM manual_shopping_items WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Deletes every checked manual item for `week_start`, leaving unchecked
+/// items untouched, and returns how many rows were removed.
+pub async fn delete_checked_items(pool: &SqlitePool, week_start: &str) -> Result<u64, AppError> {
+    let result =
+        sqlx::query("DELETE FROM manual_shopping_items WHERE week_start = ? AND is_checked = 1")
+            .bind(week_start)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected())
+}
+
+/// The `limit` most-added manual shopping items, grouped by
+/// case/whitespace-insensitive name, for "quick add" chips. Counts every
+/// row regardless of `is_checked` — a checked item was still something the
+/// user bought, which is exactly the frequency signal a quick-add chip
+/// wants, so excluding it would undercount staples that get checked off
+/// quickly. Counts soft-deleted rows too, for the same reason — a
+/// frequency signal shouldn't drop just because the row was cleaned up.
+/// `unit`/`category` are taken from the item's most recent occurrence, so a
+/// staple whose unit changed over time shows its latest one.
+pub async fn get_frequent_items(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<FrequentItem>, AppError> {
+    let items = sqlx::query_as::<_, FrequentItem>(
+        "WITH counts AS ( \
+             SELECT lower(trim(name)) AS normalized_name, count(*) AS use_count \
+             FROM manual_shopping_items \
+             GROUP BY normalized_name \
+         ), \
+         latest AS ( \
+             SELECT lower(trim(name)) AS normalized_name, name, unit, category, \
+                    row_number() OVER ( \
+                        PARTITION BY lower(trim(name)) ORDER BY created_at DESC \
+                    ) AS rank \
+             FROM manual_shopping_items \
+         ) \
+         SELECT latest.name AS name, latest.unit AS unit, latest.category AS category, \
+                counts.use_count AS use_count \
+         FROM counts \
+         JOIN latest ON latest.normalized_name = counts.normalized_name AND latest.rank = 1 \
+         ORDER BY counts.use_count DESC, latest.name \
+         LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(items)
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ListProgressCounts {
+    total: i64,
+    checked: i64,
+    deleted: i64,
+}
+
+/// How far along `week_start`'s manual shopping list is, for a progress bar
+/// ("7 of 12 items checked"). `total`/`checked` only count non-deleted
+/// items; `percent` is `0.0` rather than `NaN` when the list is empty.
+pub async fn get_list_progress(
+    pool: &SqlitePool,
+    week_start: &str,
+) -> Result<ListProgress, AppError> {
+    let counts = sqlx::query_as::<_, ListProgressCounts>(
+        "SELECT \
+             count(*) FILTER (WHERE deleted_at IS NULL) AS total, \
+             count(*) FILTER (WHERE deleted_at IS NULL AND is_checked = 1) AS checked, \
+             count(*) FILTER (WHERE deleted_at IS NOT NULL) AS deleted \
+         FROM manual_shopping_items \
+         WHERE week_start = ?",
+    )
+    .bind(week_start)
+    .fetch_one(pool)
+    .await?;
+
+    let percent = if counts.total > 0 {
+        (counts.checked as f64 / counts.total as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(ListProgress {
+        total: counts.total,
+        checked: counts.checked,
+        deleted: counts.deleted,
+        percent,
+    })
+}
+
+/// Every week that has a manual shopping list, newest first, for a "move
+/// item to another list" picker that needs to show more than just the
+/// current week. Deleted items don't count towards `item_count`, and a
+/// week with only deleted items is omitted entirely rather than shown
+/// empty.
+pub async fn get_all_lists(pool: &SqlitePool) -> Result<Vec<ShoppingListSummary>, AppError> {
+    sqlx::query_as::<_, ShoppingListSummary>(
+        "SELECT week_start, count(*) AS item_count, min(created_at) AS created_at \
+         FROM manual_shopping_items \
+         WHERE deleted_at IS NULL \
+         GROUP BY week_start \
+         ORDER BY week_start DESC, created_at",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(Into::into)
+}
+
+/// Moves a manual item onto a different week's shopping list, deleting it
+/// from its current week and inserting it into `list_id`'s, merging into a
+/// matching unchecked row there the same way [`add_shopping_item`] would.
+/// There's no shopping list type separate from a week's manual items in
+/// this app, so "list" here means `list_id`'s `week_start`; both `id` and
+/// `list_id` must already exist — the latter meaning `list_id` already has
+/// at least one non-deleted item (see [`get_all_lists`]). Runs as a single
+/// transaction so a crash partway through can't drop the item entirely.
+pub async fn promote_to_shopping_list(
+    pool: &SqlitePool,
+    id: &str,
+    list_id: &str,
+) -> Result<ManualShoppingItem, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let item = sqlx::query_as::<_, ManualShoppingItem>(
+        "SELECT * FROM manual_shopping_items WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("manual item '{id}' does not exist")))?;
+
+    let list_item_count: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM manual_shopping_items WHERE week_start = ? AND deleted_at IS NULL",
+    )
+    .bind(list_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if list_item_count == 0 {
+        return Err(AppError::NotFound(format!(
+            "shopping list '{list_id}' does not exist"
+        )));
+    }
+
+    sqlx::query("DELETE FROM manual_shopping_items WHERE id = ?")
+        .bind(&item.id)
+        .execute(&mut *tx)
+        .await?;
+
+    let normalized_name = item.name.trim().to_lowercase();
+    let candidates = sqlx::query_as::<_, ManualShoppingItem>(
+        "SELECT * FROM manual_shopping_items \
+         WHERE week_start = ? AND is_checked = 0 AND deleted_at IS NULL AND lower(name) = ?",
+    )
+    .bind(list_id)
+    .bind(&normalized_name)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut merged_into = None;
+    for existing in candidates {
+        if let Some(merged_quantity) = aggregate_quantities(
+            existing.quantity,
+            existing.unit.as_deref(),
+            item.quantity,
+            item.unit.as_deref(),
+        ) {
+            sqlx::query("UPDATE manual_shopping_items SET quantity = ? WHERE id = ?")
+                .bind(merged_quantity)
+                .bind(&existing.id)
+                .execute(&mut *tx)
+                .await?;
+            merged_into = Some(existing.id);
+            break;
+        }
+    }
+
+    let promoted_id = match merged_into {
+        Some(id) => id,
+        None => {
+            let new_id = Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO manual_shopping_items (id, week_start, name, quantity, unit, \
+                 category) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&new_id)
+            .bind(list_id)
+            .bind(&item.name)
+            .bind(item.quantity)
+            .bind(&item.unit)
+            .bind(&item.category)
+            .execute(&mut *tx)
+            .await?;
+            new_id
+        }
+    };
+
+    let promoted =
+        sqlx::query_as::<_, ManualShoppingItem>("SELECT * FROM manual_shopping_items WHERE id = ?")
+            .bind(&promoted_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| {
+                AppError::Internal("manual item vanished immediately after promotion".to_string())
+            })?;
+
+    tx.commit().await?;
+    Ok(promoted)
+}
+
+/// Compares two weeks' manual shopping lists — say a "planned" list against
+/// what actually got bought — matching items on (singularized) name and a
+/// [`convert_quantity`]-compatible unit. An item with no match on the other
+/// list falls into `only_in_a`/`only_in_b`; a matched pair whose quantities
+/// disagree (after converting `list_b`'s quantity into `list_a`'s unit)
+/// lands in `differing` instead of being silently treated as equal.
+pub async fn diff_lists(
+    pool: &SqlitePool,
+    list_a: &str,
+    list_b: &str,
+) -> Result<ListDiff, AppError> {
+    let items_a = list_items_for_week(pool, list_a).await?;
+    let items_b = list_items_for_week(pool, list_b).await?;
+
+    let mut only_in_a = Vec::new();
+    let mut differing = Vec::new();
+    let mut matched_b_ids = Vec::new();
+
+    for item_a in &items_a {
+        let normalized_name = singularize_ingredient_name(&item_a.name);
+        let match_b = items_b.iter().find(|item_b| {
+            !matched_b_ids.contains(&item_b.id)
+                && singularize_ingredient_name(&item_b.name) == normalized_name
+                && convert_quantity(
+                    item_b.quantity.unwrap_or(0.0),
+                    item_b.unit.as_deref().unwrap_or(""),
+                    item_a.unit.as_deref().unwrap_or(""),
+                )
+                .is_some()
+        });
+
+        match match_b {
+            Some(item_b) => {
+                matched_b_ids.push(item_b.id.clone());
+                let quantity_a = item_a.quantity.unwrap_or(0.0);
+                let quantity_b_in_a_unit = convert_quantity(
+                    item_b.quantity.unwrap_or(0.0),
+                    item_b.unit.as_deref().unwrap_or(""),
+                    item_a.unit.as_deref().unwrap_or(""),
+                )
+                .unwrap_or(0.0);
+                if (quantity_a - quantity_b_in_a_unit).abs() > 1e-9 {
+                    differing.push(ShoppingListQuantityDiff {
+                        name: item_a.name.clone(),
+                        quantity_a,
+                        quantity_b: item_b.quantity.unwrap_or(0.0),
+                        unit: item_a.unit.clone().unwrap_or_default(),
+                    });
+                }
+            }
+            None => only_in_a.push(item_a.clone()),
+        }
+    }
+
+    let only_in_b = items_b
+        .into_iter()
+        .filter(|item_b| !matched_b_ids.contains(&item_b.id))
+        .collect();
+
+    Ok(ListDiff {
+        only_in_a,
+        only_in_b,
+        differing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+
+    #[tokio::test]
+    async fn delete_checked_items_only_removes_checked_rows() {
+        let pool = init_db_for_test().await;
+        let week = "2026-05-04";
+
+        let checked_one = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "manual-clear-test Paper Towels".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        let checked_two = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "manual-clear-test Dish Soap".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        let unchecked = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "manual-clear-test Sponges".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        update_manual_item(
+            &pool,
+            &checked_one.id,
+            ManualShoppingItemUpdate {
+                is_checked: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        update_manual_item(
+            &pool,
+            &checked_two.id,
+            ManualShoppingItemUpdate {
+                is_checked: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let removed = delete_checked_items(&pool, week).await.unwrap();
+        assert_eq!(removed, 2);
+
+        assert!(get_manual_item_by_id(&pool, &checked_one.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(get_manual_item_by_id(&pool, &checked_two.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(get_manual_item_by_id(&pool, &unchecked.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn delete_checked_items_returns_zero_when_none_checked() {
+        let pool = init_db_for_test().await;
+        let week = "2026-05-11";
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "manual-clear-test-empty Bread".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let removed = delete_checked_items(&pool, week).await.unwrap();
+        assert_eq!(removed, 0);
+    }
+
+    #[tokio::test]
+    async fn add_shopping_item_merges_compatible_duplicates() {
+        let pool = init_db_for_test().await;
+        let week = "2026-06-01";
+
+        let first = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-test flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        let second = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-test flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.quantity, Some(4.0));
+    }
+
+    #[tokio::test]
+    async fn add_shopping_item_keeps_incompatible_units_separate() {
+        let pool = init_db_for_test().await;
+        let week = "2026-06-08";
+
+        let first = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-test butter".to_string(),
+                quantity: Some(1.0),
+                unit: Some("lb".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        let second = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-test butter".to_string(),
+                quantity: Some(1.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_ne!(second.id, first.id);
+    }
+
+    #[tokio::test]
+    async fn get_frequent_items_orders_by_use_count_descending() {
+        let pool = init_db_for_test().await;
+        let week = "2026-07-06";
+
+        for _ in 0..3 {
+            create_manual_item(
+                &pool,
+                ManualShoppingItemInput {
+                    week_start: week.to_string(),
+                    name: "frequent-test Milk".to_string(),
+                    quantity: Some(1.0),
+                    unit: Some("gallon".to_string()),
+                    category: Some("Dairy".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        for _ in 0..2 {
+            create_manual_item(
+                &pool,
+                ManualShoppingItemInput {
+                    week_start: week.to_string(),
+                    name: "Frequent-Test milk".to_string(),
+                    quantity: Some(1.0),
+                    unit: Some("gallon".to_string()),
+                    category: Some("Dairy".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "frequent-test Eggs".to_string(),
+                quantity: Some(1.0),
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let frequent = get_frequent_items(&pool, 1).await.unwrap();
+
+        assert_eq!(frequent.len(), 1);
+        assert_eq!(frequent[0].name.to_lowercase(), "frequent-test milk");
+        assert_eq!(frequent[0].use_count, 5);
+    }
+
+    #[tokio::test]
+    async fn soft_delete_sets_deleted_at_and_restore_clears_it() {
+        let pool = init_db_for_test().await;
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-03".to_string(),
+                name: "soft-delete-test Oats".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(item.deleted_at, None);
+
+        soft_delete_shopping_item(&pool, &item.id).await.unwrap();
+        let deleted = get_manual_item_by_id(&pool, &item.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(deleted.deleted_at.is_some());
+
+        restore_shopping_item(&pool, &item.id).await.unwrap();
+        let restored = get_manual_item_by_id(&pool, &item.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.deleted_at, None);
+    }
+
+    #[tokio::test]
+    async fn soft_delete_of_a_missing_item_is_not_found() {
+        let pool = init_db_for_test().await;
+
+        let result = soft_delete_shopping_item(&pool, "does-not-exist").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn hard_deleting_a_soft_deleted_item_removes_its_row() {
+        let pool = init_db_for_test().await;
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-03".to_string(),
+                name: "hard-delete-test Oats".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &item.id).await.unwrap();
+
+        hard_delete_shopping_item(&pool, &item.id).await.unwrap();
+
+        assert!(get_manual_item_by_id(&pool, &item.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn hard_deleting_an_active_item_is_rejected() {
+        let pool = init_db_for_test().await;
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-03".to_string(),
+                name: "hard-delete-test Rice".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = hard_delete_shopping_item(&pool, &item.id).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(get_manual_item_by_id(&pool, &item.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn hard_delete_of_a_missing_item_is_not_found() {
+        let pool = init_db_for_test().await;
+
+        let result = hard_delete_shopping_item(&pool, "does-not-exist").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn toggling_twice_returns_to_the_original_checked_state() {
+        let pool = init_db_for_test().await;
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-03".to_string(),
+                name: "toggle-test Oats".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!item.is_checked);
+
+        let toggled_once = toggle_item_checked(&pool, &item.id).await.unwrap();
+        assert!(toggled_once.is_checked);
+
+        let toggled_twice = toggle_item_checked(&pool, &item.id).await.unwrap();
+        assert_eq!(toggled_twice.is_checked, item.is_checked);
+    }
+
+    #[tokio::test]
+    async fn toggling_a_missing_item_is_not_found() {
+        let pool = init_db_for_test().await;
+
+        let result = toggle_item_checked(&pool, "does-not-exist").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn merging_skips_a_soft_deleted_candidate() {
+        let pool = init_db_for_test().await;
+        let week = "2026-08-10";
+
+        let first = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-skip-test flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &first.id).await.unwrap();
+
+        let second = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "merge-skip-test flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_ne!(second.id, first.id);
+    }
+
+    #[tokio::test]
+    async fn progress_reports_a_partially_checked_list() {
+        let pool = init_db_for_test().await;
+        let week = "2026-08-17";
+
+        for i in 0..5 {
+            let item = create_manual_item(
+                &pool,
+                ManualShoppingItemInput {
+                    week_start: week.to_string(),
+                    name: format!("progress-test item {i}"),
+                    quantity: None,
+                    unit: None,
+                    category: None,
+                },
+            )
+            .await
+            .unwrap();
+            if i < 2 {
+                update_manual_item(
+                    &pool,
+                    &item.id,
+                    ManualShoppingItemUpdate {
+                        is_checked: Some(true),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            }
+        }
+
+        let progress = get_list_progress(&pool, week).await.unwrap();
+
+        assert_eq!(progress.total, 5);
+        assert_eq!(progress.checked, 2);
+        assert_eq!(progress.deleted, 0);
+        assert_eq!(progress.percent, 40.0);
+    }
+
+    #[tokio::test]
+    async fn progress_reports_one_hundred_percent_when_fully_checked() {
+        let pool = init_db_for_test().await;
+        let week = "2026-08-24";
+
+        for i in 0..3 {
+            let item = create_manual_item(
+                &pool,
+                ManualShoppingItemInput {
+                    week_start: week.to_string(),
+                    name: format!("progress-full-test item {i}"),
+                    quantity: None,
+                    unit: None,
+                    category: None,
+                },
+            )
+            .await
+            .unwrap();
+            update_manual_item(
+                &pool,
+                &item.id,
+                ManualShoppingItemUpdate {
+                    is_checked: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let progress = get_list_progress(&pool, week).await.unwrap();
+
+        assert_eq!(progress.total, 3);
+        assert_eq!(progress.checked, 3);
+        assert_eq!(progress.percent, 100.0);
+    }
+
+    #[tokio::test]
+    async fn progress_of_an_empty_list_is_zero_percent_not_nan() {
+        let pool = init_db_for_test().await;
+
+        let progress = get_list_progress(&pool, "2026-08-31").await.unwrap();
+
+        assert_eq!(progress.total, 0);
+        assert_eq!(progress.checked, 0);
+        assert_eq!(progress.percent, 0.0);
+    }
+
+    #[tokio::test]
+    async fn progress_excludes_soft_deleted_items_from_the_total() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-07";
+
+        let kept = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "progress-deleted-test Kept".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        let removed = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "progress-deleted-test Removed".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &removed.id).await.unwrap();
+        update_manual_item(
+            &pool,
+            &kept.id,
+            ManualShoppingItemUpdate {
+                is_checked: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let progress = get_list_progress(&pool, week).await.unwrap();
+
+        assert_eq!(progress.total, 1);
+        assert_eq!(progress.checked, 1);
+        assert_eq!(progress.deleted, 1);
+        assert_eq!(progress.percent, 100.0);
+    }
+
+    #[tokio::test]
+    async fn get_all_lists_returns_every_week_with_items_newest_first() {
+        let pool = init_db_for_test().await;
+
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-05-04".to_string(),
+                name: "all-lists-test Flour".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-05-11".to_string(),
+                name: "all-lists-test Sugar".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-05-11".to_string(),
+                name: "all-lists-test Butter".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let lists = get_all_lists(&pool).await.unwrap();
+
+        assert_eq!(lists.len(), 2);
+        assert_eq!(lists[0].week_start, "2026-05-11");
+        assert_eq!(lists[0].item_count, 2);
+        assert_eq!(lists[1].week_start, "2026-05-04");
+        assert_eq!(lists[1].item_count, 1);
+    }
+
+    #[tokio::test]
+    async fn get_all_lists_omits_a_week_whose_only_item_was_deleted() {
+        let pool = init_db_for_test().await;
+        let week = "2026-05-18";
+
+        let removed = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "all-lists-test Removed".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &removed.id).await.unwrap();
+
+        let lists = get_all_lists(&pool).await.unwrap();
+
+        assert!(!lists.iter().any(|list| list.week_start == week));
+    }
+
+    #[tokio::test]
+    async fn promoting_moves_an_item_to_the_target_week_and_off_the_source() {
+        let pool = init_db_for_test().await;
+        let source_week = "2026-09-14";
+        let target_week = "2026-09-21";
+
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: source_week.to_string(),
+                name: "promote-test Rice".to_string(),
+                quantity: Some(1.0),
+                unit: Some("lb".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: target_week.to_string(),
+                name: "promote-test anchor".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let promoted = promote_to_shopping_list(&pool, &item.id, target_week)
+            .await
+            .unwrap();
+
+        assert_eq!(promoted.week_start, target_week);
+        assert_eq!(promoted.name, "promote-test Rice");
+        assert!(get_manual_item_by_id(&pool, &item.id)
+            .await
+            .unwrap()
+            .is_none());
+        let on_target = list_items_for_week(&pool, target_week).await.unwrap();
+        assert!(on_target.iter().any(|i| i.name == "promote-test Rice"));
+    }
+
+    #[tokio::test]
+    async fn promoting_rejects_an_unknown_item() {
+        let pool = init_db_for_test().await;
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-09-28".to_string(),
+                name: "promote-missing-test anchor".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = promote_to_shopping_list(&pool, "does-not-exist", "2026-09-28").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn promoting_rejects_an_unknown_target_list() {
+        let pool = init_db_for_test().await;
+        let item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-10-05".to_string(),
+                name: "promote-bad-target-test Beans".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = promote_to_shopping_list(&pool, &item.id, "2099-01-01").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn diff_lists_buckets_items_shared_only_in_a_only_in_b_and_differing() {
+        let pool = init_db_for_test().await;
+        let list_a = "2026-11-02";
+        let list_b = "2026-11-09";
+
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_a.to_string(),
+                name: "diff-test Flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_a.to_string(),
+                name: "diff-test Eggs".to_string(),
+                quantity: Some(6.0),
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_a.to_string(),
+                name: "diff-test Butter".to_string(),
+                quantity: Some(1.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_b.to_string(),
+                name: "diff-test Flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_b.to_string(),
+                name: "diff-test Eggs".to_string(),
+                quantity: Some(12.0),
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: list_b.to_string(),
+                name: "diff-test Milk".to_string(),
+                quantity: Some(1.0),
+                unit: Some("quart".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let diff = diff_lists(&pool, list_a, list_b).await.unwrap();
+
+        assert_eq!(diff.only_in_a.len(), 1);
+        assert_eq!(diff.only_in_a[0].name, "diff-test Butter");
+
+        assert_eq!(diff.only_in_b.len(), 1);
+        assert_eq!(diff.only_in_b[0].name, "diff-test Milk");
+
+        assert_eq!(diff.differing.len(), 1);
+        assert_eq!(diff.differing[0].name, "diff-test Eggs");
+        assert_eq!(diff.differing[0].quantity_a, 6.0);
+        assert_eq!(diff.differing[0].quantity_b, 12.0);
+    }
+}
@@ -0,0 +1,647 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::db::recipes::get_recipe_by_id;
+use crate::error::AppError;
+use crate::models::{
+    DayTimeTotal, MealPlan, MealPlanInput, MealPlanTemplateEntry, MealPlanWithRecipe,
+};
+
+/// Creates a meal plan entry. When `input.servings` is omitted, defaults to
+/// the planned recipe's own `servings` (e.g. a dinner recipe plopped onto
+/// breakfast without the user having to think about portion size first).
+pub async fn create_meal_plan(
+    pool: &SqlitePool,
+    input: MealPlanInput,
+) -> Result<MealPlan, AppError> {
+    let recipe = get_recipe_by_id(pool, &input.recipe_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("recipe '{}' does not exist", input.recipe_id))
+        })?;
+    let servings = input.servings.unwrap_or(recipe.servings);
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO meal_plans (id, recipe_id, date, meal_type, servings) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&input.recipe_id)
+    .bind(&input.date)
+    .bind(&input.meal_type)
+    .bind(servings)
+    .execute(pool)
+    .await?;
+
+    get_meal_plan_by_id(pool, &id).await?.ok_or_else(|| {
+        AppError::Internal("meal plan vanished immediately after insert".to_string())
+    })
+}
+
+/// Applies a repeating "template week" to the week starting `week_start`:
+/// each entry's `day_offset` (0-6) is added to `week_start` to get its
+/// actual date, and the resulting meal plan is inserted within a single
+/// transaction, so a crash partway through can't leave half the week
+/// planned. An entry that collides with an already-planned `(date,
+/// meal_type)` slot is silently skipped rather than failing the whole
+/// template — unlike [`defer_meal_plan`], which surfaces the same
+/// collision as an [`AppError::Conflict`], since here the caller is
+/// applying a reusable template rather than rescheduling one specific plan.
+pub async fn apply_template(
+    pool: &SqlitePool,
+    template: Vec<MealPlanTemplateEntry>,
+    week_start: &str,
+) -> Result<Vec<MealPlan>, AppError> {
+    let start = NaiveDate::parse_from_str(week_start, "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("'{week_start}' is not a valid date")))?;
+
+    let mut tx = pool.begin().await?;
+    let mut created = Vec::new();
+
+    for entry in template {
+        if !(0..=6).contains(&entry.day_offset) {
+            return Err(AppError::Validation(format!(
+                "day offset {} is out of range (must be 0-6)",
+                entry.day_offset
+            )));
+        }
+        let date = (start + Duration::days(entry.day_offset))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let recipe_servings: Option<i64> =
+            sqlx::query_scalar("SELECT servings FROM recipes WHERE id = ?")
+                .bind(&entry.recipe_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        let Some(recipe_servings) = recipe_servings else {
+            return Err(AppError::NotFound(format!(
+                "recipe '{}' does not exist",
+                entry.recipe_id
+            )));
+        };
+        let servings = entry.servings.unwrap_or(recipe_servings);
+
+        let id = Uuid::new_v4().to_string();
+        let inserted = sqlx::query(
+            "INSERT INTO meal_plans (id, recipe_id, date, meal_type, servings) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&entry.recipe_id)
+        .bind(&date)
+        .bind(&entry.meal_type)
+        .bind(servings)
+        .execute(&mut *tx)
+        .await;
+
+        match inserted {
+            Ok(_) => {
+                let plan = sqlx::query_as::<_, MealPlan>("SELECT * FROM meal_plans WHERE id = ?")
+                    .bind(&id)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::Internal(
+                            "meal plan vanished immediately after insert".to_string(),
+                        )
+                    })?;
+                created.push(plan);
+            }
+            Err(err)
+                if err
+                    .as_database_error()
+                    .is_some_and(|e| e.is_unique_violation()) =>
+            {
+                continue;
+            }
+            Err(err) => return Err(AppError::Database(err)),
+        }
+    }
+
+    tx.commit().await?;
+    Ok(created)
+}
+
+/// Reschedules `id` to `new_date`, keeping its meal type and servings — for
+/// "this got skipped, push it to another day" rather than buying the same
+/// ingredients again. `(date, meal_type)` is unique, so a collision with
+/// another entry already on `new_date` surfaces as a friendly
+/// [`AppError::Conflict`] instead of the raw database error.
+pub async fn defer_meal_plan(
+    pool: &SqlitePool,
+    id: &str,
+    new_date: &str,
+) -> Result<MealPlan, AppError> {
+    let existing = get_meal_plan_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("meal plan '{id}' does not exist")))?;
+
+    sqlx::query("UPDATE meal_plans SET date = ? WHERE id = ?")
+        .bind(new_date)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|err| match err.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => AppError::Conflict(format!(
+                "a {} is already planned for {new_date}",
+                existing.meal_type
+            )),
+            _ => AppError::Database(err),
+        })?;
+
+    get_meal_plan_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::Internal("meal plan vanished immediately after defer".to_string()))
+}
+
+pub async fn get_meal_plan_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<MealPlan>, AppError> {
+    let plan = sqlx::query_as::<_, MealPlan>("SELECT * FROM meal_plans WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(plan)
+}
+
+/// Returns every non-deleted meal-plan entry for `recipe_id`, ordered by
+/// date, so a recipe detail view can show "planned for Jan 15 (dinner)".
+pub async fn get_occurrences_for_recipe(
+    pool: &SqlitePool,
+    recipe_id: &str,
+) -> Result<Vec<MealPlan>, AppError> {
+    let plans = sqlx::query_as::<_, MealPlan>(
+        "SELECT * FROM meal_plans WHERE recipe_id = ? AND is_deleted = 0 ORDER BY date",
+    )
+    .bind(recipe_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(plans)
+}
+
+/// Sums `prep_time + cook_time` (in minutes) across all planned recipes per
+/// date in `[start_date, end_date]`, for "Sunday: 2h 15m total cooking"
+/// style planning screens. Dates with no plans are simply absent.
+pub async fn get_daily_time_totals(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<DayTimeTotal>, AppError> {
+    let totals = sqlx::query_as::<_, DayTimeTotal>(
+        "SELECT mp.date AS date, \
+                sum(coalesce(r.prep_time, 0) + coalesce(r.cook_time, 0)) AS total_minutes \
+         FROM meal_plans mp \
+         JOIN recipes r ON r.id = mp.recipe_id \
+         WHERE mp.is_deleted = 0 AND mp.date BETWEEN ? AND ? \
+         GROUP BY mp.date \
+         ORDER BY mp.date",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await?;
+    Ok(totals)
+}
+
+/// Like [`get_meal_plans_with_recipes`] but additionally filtered to
+/// recipes whose name contains `name` (case-insensitive), for "when did I
+/// last plan spaghetti?" searches that don't want to cross-reference every
+/// plan against the recipe list client-side.
+pub async fn find_plans_by_recipe_name(
+    pool: &SqlitePool,
+    name: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<MealPlanWithRecipe>, AppError> {
+    let normalized = name.trim().to_lowercase();
+    let pattern = format!("%{normalized}%");
+
+    let plans = sqlx::query_as::<_, MealPlanWithRecipe>(
+        "SELECT mp.id AS id, mp.recipe_id AS recipe_id, mp.date AS date, \
+                mp.meal_type AS meal_type, mp.servings AS servings, \
+                r.name AS recipe_name, r.image_path AS recipe_image_path \
+         FROM meal_plans mp \
+         JOIN recipes r ON r.id = mp.recipe_id \
+         WHERE mp.is_deleted = 0 AND mp.date BETWEEN ? AND ? \
+           AND lower(r.name) LIKE ? \
+         ORDER BY mp.date",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .bind(pattern)
+    .fetch_all(pool)
+    .await?;
+    Ok(plans)
+}
+
+/// Like [`get_occurrences_for_recipe`] but across every recipe in
+/// `[start_date, end_date]`, with each plan's recipe name and image inlined
+/// via a join — see [`MealPlanWithRecipe`].
+pub async fn get_meal_plans_with_recipes(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<MealPlanWithRecipe>, AppError> {
+    let plans = sqlx::query_as::<_, MealPlanWithRecipe>(
+        "SELECT mp.id AS id, mp.recipe_id AS recipe_id, mp.date AS date, \
+                mp.meal_type AS meal_type, mp.servings AS servings, \
+                r.name AS recipe_name, r.image_path AS recipe_image_path \
+         FROM meal_plans mp \
+         JOIN recipes r ON r.id = mp.recipe_id \
+         WHERE mp.is_deleted = 0 AND mp.date BETWEEN ? AND ? \
+         ORDER BY mp.date",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await?;
+    Ok(plans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+    use crate::db::recipes::create_recipe;
+    use crate::models::RecipeInput;
+
+    fn sample_recipe_input() -> RecipeInput {
+        RecipeInput {
+            name: "Pancakes".to_string(),
+            description: None,
+            servings: 4,
+            yield_unit: None,
+            prep_time: Some(10),
+            cook_time: Some(15),
+            instructions: vec!["Mix".into(), "Cook".into()],
+            image_path: None,
+            source_url: None,
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_meal_plan_succeeds_for_existing_recipe() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input()).await.unwrap();
+
+        let plan = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-01-15".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.recipe_id, recipe.id);
+    }
+
+    #[tokio::test]
+    async fn create_meal_plan_rejects_nonexistent_recipe() {
+        let pool = init_db_for_test().await;
+
+        let result = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: Uuid::new_v4().to_string(),
+                date: "2026-01-15".to_string(),
+                meal_type: "lunch".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn get_occurrences_for_recipe_returns_dates_in_order() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input()).await.unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-02-02".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-02-01".to_string(),
+                meal_type: "lunch".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+        let occurrences = get_occurrences_for_recipe(&pool, &recipe.id).await.unwrap();
+
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(occurrences[0].date, "2026-02-01");
+        assert_eq!(occurrences[1].date, "2026-02-02");
+    }
+
+    #[tokio::test]
+    async fn get_daily_time_totals_sums_same_day_recipes() {
+        let pool = init_db_for_test().await;
+
+        let mut quick = sample_recipe_input();
+        quick.name = "Time Totals Quick Soup".to_string();
+        quick.prep_time = Some(10);
+        quick.cook_time = Some(20);
+        let quick = create_recipe(&pool, quick).await.unwrap();
+
+        let mut slow = sample_recipe_input();
+        slow.name = "Time Totals Slow Roast".to_string();
+        slow.prep_time = Some(15);
+        slow.cook_time = Some(90);
+        let slow = create_recipe(&pool, slow).await.unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: quick.id.clone(),
+                date: "2026-03-01".to_string(),
+                meal_type: "lunch".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: slow.id.clone(),
+                date: "2026-03-01".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let totals = get_daily_time_totals(&pool, "2026-03-01", "2026-03-07")
+            .await
+            .unwrap();
+
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].date, "2026-03-01");
+        assert_eq!(totals[0].total_minutes, 10 + 20 + 15 + 90);
+    }
+
+    #[tokio::test]
+    async fn get_daily_time_totals_omits_days_with_no_plans() {
+        let pool = init_db_for_test().await;
+        let totals = get_daily_time_totals(&pool, "2026-03-10", "2026-03-12")
+            .await
+            .unwrap();
+        assert!(totals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_meal_plan_defaults_servings_to_recipe_servings() {
+        let pool = init_db_for_test().await;
+        let mut recipe_input = sample_recipe_input();
+        recipe_input.name = "Servings Default Waffles".to_string();
+        recipe_input.servings = 6;
+        let recipe = create_recipe(&pool, recipe_input).await.unwrap();
+
+        let plan = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-04-01".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.servings, 6);
+    }
+
+    #[tokio::test]
+    async fn create_meal_plan_uses_explicit_servings_when_given() {
+        let pool = init_db_for_test().await;
+        let mut recipe_input = sample_recipe_input();
+        recipe_input.name = "Servings Explicit Waffles".to_string();
+        recipe_input.servings = 6;
+        let recipe = create_recipe(&pool, recipe_input).await.unwrap();
+
+        let plan = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-04-02".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.servings, 2);
+    }
+
+    #[tokio::test]
+    async fn defer_meal_plan_reschedules_to_an_open_date() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input()).await.unwrap();
+
+        let plan = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-05-01".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let deferred = defer_meal_plan(&pool, &plan.id, "2026-05-05")
+            .await
+            .unwrap();
+
+        assert_eq!(deferred.id, plan.id);
+        assert_eq!(deferred.date, "2026-05-05");
+        assert_eq!(deferred.meal_type, "dinner");
+    }
+
+    #[tokio::test]
+    async fn find_plans_by_recipe_name_returns_only_matching_plans_in_range() {
+        let pool = init_db_for_test().await;
+
+        let mut spaghetti = sample_recipe_input();
+        spaghetti.name = "Spaghetti Bolognese".to_string();
+        let spaghetti = create_recipe(&pool, spaghetti).await.unwrap();
+
+        let mut tacos = sample_recipe_input();
+        tacos.name = "Fish Tacos".to_string();
+        let tacos = create_recipe(&pool, tacos).await.unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: spaghetti.id.clone(),
+                date: "2026-06-02".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: spaghetti.id.clone(),
+                date: "2026-06-20".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: tacos.id.clone(),
+                date: "2026-06-03".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let matches = find_plans_by_recipe_name(&pool, "spaghetti", "2026-06-01", "2026-06-10")
+            .await
+            .unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].date, "2026-06-02");
+        assert_eq!(matches[0].recipe_name, "Spaghetti Bolognese");
+    }
+
+    #[tokio::test]
+    async fn apply_template_computes_dates_and_skips_a_conflicting_slot() {
+        let pool = init_db_for_test().await;
+
+        let mut taco = sample_recipe_input();
+        taco.name = "Template Tacos".to_string();
+        taco.servings = 4;
+        let taco = create_recipe(&pool, taco).await.unwrap();
+
+        let mut pasta = sample_recipe_input();
+        pasta.name = "Template Pasta".to_string();
+        pasta.servings = 2;
+        let pasta = create_recipe(&pool, pasta).await.unwrap();
+
+        // Already planned on day offset 2 (2026-07-03), so the template's
+        // entry for that day should be skipped rather than erroring.
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: pasta.id.clone(),
+                date: "2026-07-03".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+        let template = vec![
+            MealPlanTemplateEntry {
+                day_offset: 1,
+                meal_type: "dinner".to_string(),
+                recipe_id: taco.id.clone(),
+                servings: None,
+            },
+            MealPlanTemplateEntry {
+                day_offset: 2,
+                meal_type: "dinner".to_string(),
+                recipe_id: pasta.id.clone(),
+                servings: None,
+            },
+            MealPlanTemplateEntry {
+                day_offset: 5,
+                meal_type: "lunch".to_string(),
+                recipe_id: taco.id.clone(),
+                servings: Some(3),
+            },
+        ];
+
+        let created = apply_template(&pool, template, "2026-07-01").await.unwrap();
+
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].date, "2026-07-02");
+        assert_eq!(created[0].recipe_id, taco.id);
+        assert_eq!(created[0].servings, 4);
+        assert_eq!(created[1].date, "2026-07-06");
+        assert_eq!(created[1].servings, 3);
+
+        let tacos_planned = get_occurrences_for_recipe(&pool, &taco.id).await.unwrap();
+        assert_eq!(tacos_planned.len(), 2);
+
+        let pasta_planned = get_occurrences_for_recipe(&pool, &pasta.id).await.unwrap();
+        assert_eq!(pasta_planned.len(), 1);
+        assert_eq!(pasta_planned[0].date, "2026-07-03");
+    }
+
+    #[tokio::test]
+    async fn defer_meal_plan_rejects_a_colliding_date() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input()).await.unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-05-10".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let plan_to_defer = create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-05-09".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = defer_meal_plan(&pool, &plan_to_defer.id, "2026-05-10").await;
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+}
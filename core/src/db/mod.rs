@@ -0,0 +1,12 @@
+pub mod idempotency;
+pub mod ingredients;
+pub mod manual_items;
+pub mod meal_plans;
+pub mod pantry;
+pub mod pool;
+pub mod purge;
+pub mod quick_lists;
+pub mod recipes;
+pub mod settings;
+pub mod shopping_list;
+pub mod shopping_lists;
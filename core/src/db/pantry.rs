@@ -0,0 +1,234 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{PantryItem, PantryItemInput, RestockSuggestion};
+
+pub async fn add_pantry_item(
+    pool: &SqlitePool,
+    input: PantryItemInput,
+) -> Result<PantryItem, AppError> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO pantry_items (id, name, quantity, unit, category, restock_threshold) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&input.name)
+    .bind(input.quantity)
+    .bind(&input.unit)
+    .bind(&input.category)
+    .bind(input.restock_threshold)
+    .execute(pool)
+    .await?;
+
+    get_pantry_item_by_id(pool, &id).await?.ok_or_else(|| {
+        AppError::Internal("pantry item vanished immediately after insert".to_string())
+    })
+}
+
+pub async fn get_pantry_item_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<PantryItem>, AppError> {
+    let item = sqlx::query_as::<_, PantryItem>("SELECT * FROM pantry_items WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(item)
+}
+
+/// Every pantry item on hand, for stock-aware shopping calculations like
+/// [`crate::db::shopping_list::get_week_shopping_gap`].
+pub async fn list_pantry_items(pool: &SqlitePool) -> Result<Vec<PantryItem>, AppError> {
+    let items = sqlx::query_as::<_, PantryItem>("SELECT * FROM pantry_items ORDER BY name")
+        .fetch_all(pool)
+        .await?;
+    Ok(items)
+}
+
+/// Pantry items that have run down to (or below) their `restock_threshold`,
+/// each paired with how much more is needed to bring the item back up to
+/// that threshold. Items without a configured threshold are never
+/// flagged.
+pub async fn suggest_restock(pool: &SqlitePool) -> Result<Vec<RestockSuggestion>, AppError> {
+    let items = sqlx::query_as::<_, PantryItem>(
+        "SELECT * FROM pantry_items WHERE restock_threshold IS NOT NULL ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let suggestions = items
+        .into_iter()
+        .filter_map(|item| {
+            let threshold = item.restock_threshold?;
+            let current_quantity = item.quantity.unwrap_or(0.0);
+            if current_quantity > threshold {
+                return None;
+            }
+            Some(RestockSuggestion {
+                pantry_item_id: item.id,
+                name: item.name,
+                unit: item.unit,
+                current_quantity,
+                restock_threshold: threshold,
+                suggested_quantity: threshold - current_quantity,
+            })
+        })
+        .collect();
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+
+    #[tokio::test]
+    async fn add_and_fetch_a_pantry_item() {
+        let pool = init_db_for_test().await;
+
+        let added = add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "pantry-test Flour".to_string(),
+                quantity: Some(5.0),
+                unit: Some("cup".to_string()),
+                category: Some("Baking".to_string()),
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let fetched = get_pantry_item_by_id(&pool, &added.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.name, "pantry-test Flour");
+        assert_eq!(fetched.quantity, Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn list_pantry_items_orders_by_name() {
+        let pool = init_db_for_test().await;
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "pantry-list-test Zucchini".to_string(),
+                quantity: Some(1.0),
+                unit: None,
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "pantry-list-test Apples".to_string(),
+                quantity: Some(3.0),
+                unit: None,
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = list_pantry_items(&pool).await.unwrap();
+        let names: Vec<_> = items
+            .iter()
+            .map(|item| item.name.as_str())
+            .filter(|name| name.starts_with("pantry-list-test"))
+            .collect();
+        assert_eq!(
+            names,
+            vec!["pantry-list-test Apples", "pantry-list-test Zucchini"]
+        );
+    }
+
+    #[tokio::test]
+    async fn fetching_a_missing_pantry_item_returns_none() {
+        let pool = init_db_for_test().await;
+
+        let result = get_pantry_item_by_id(&pool, "does-not-exist")
+            .await
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn an_item_below_its_threshold_suggests_a_restock_quantity() {
+        let pool = init_db_for_test().await;
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "restock-test Olive Oil".to_string(),
+                quantity: Some(1.0),
+                unit: Some("bottle".to_string()),
+                category: None,
+                restock_threshold: Some(3.0),
+            },
+        )
+        .await
+        .unwrap();
+
+        let suggestions = suggest_restock(&pool).await.unwrap();
+        let suggestion = suggestions
+            .iter()
+            .find(|s| s.name == "restock-test Olive Oil")
+            .unwrap();
+        assert_eq!(suggestion.current_quantity, 1.0);
+        assert_eq!(suggestion.restock_threshold, 3.0);
+        assert_eq!(suggestion.suggested_quantity, 2.0);
+    }
+
+    #[tokio::test]
+    async fn an_item_above_its_threshold_is_not_suggested() {
+        let pool = init_db_for_test().await;
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "restock-test Rice".to_string(),
+                quantity: Some(10.0),
+                unit: Some("cup".to_string()),
+                category: None,
+                restock_threshold: Some(2.0),
+            },
+        )
+        .await
+        .unwrap();
+
+        let suggestions = suggest_restock(&pool).await.unwrap();
+        assert!(!suggestions.iter().any(|s| s.name == "restock-test Rice"));
+    }
+
+    #[tokio::test]
+    async fn a_threshold_less_item_is_skipped() {
+        let pool = init_db_for_test().await;
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "restock-test Vanilla".to_string(),
+                quantity: Some(0.0),
+                unit: None,
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let suggestions = suggest_restock(&pool).await.unwrap();
+        assert!(!suggestions
+            .iter()
+            .any(|s| s.name == "restock-test Vanilla"));
+    }
+}
@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use crate::error::AppError;
+
+/// Size of the connection pool every [`init_db`] call hands out. Kept in one
+/// place, rather than a bare literal in [`init_db`], so
+/// [`crate::backup::MAX_CONCURRENT_IMPORTS`] can cite it in its own doc
+/// comment instead of the two numbers silently drifting apart.
+pub(crate) const MAX_CONNECTIONS: u32 = 5;
+
+/// Opens (creating if necessary) the SQLite database at `database_url`,
+/// runs pending migrations, and sweeps out any soft-deleted shopping items
+/// that have aged past their retention window (see
+/// [`crate::db::purge::purge_old_soft_deleted_shopping_items`]). The caller
+/// owns the returned pool — typically by handing it to Tauri's managed
+/// state in `setup` — rather than it living behind a process-wide global,
+/// so independent pools (e.g. one per test) never interfere with each
+/// other.
+pub async fn init_db(database_url: &str) -> Result<SqlitePool, AppError> {
+    // SQLite only enforces FK constraints (and thus ON DELETE CASCADE) when
+    // this pragma is set, and it's per-connection rather than persisted in
+    // the database file, so every pooled connection needs it.
+    let options = SqliteConnectOptions::from_str(database_url)
+        .map_err(|e| AppError::Internal(format!("invalid database url: {e}")))?
+        .create_if_missing(true)
+        .pragma("foreign_keys", "ON");
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .connect_with(options)
+        .await?;
+
+    sqlx::migrate!("./migrations").run(&pool).await?;
+    crate::db::purge::purge_old_soft_deleted_shopping_items(&pool, chrono::Utc::now()).await?;
+
+    Ok(pool)
+}
+
+/// Like [`init_db`], but if the database file is corrupt or its migrations
+/// fail to apply, backs the bad file up to `<path>.corrupt-<timestamp>`,
+/// recreates a fresh database at the original path, and retries once. This
+/// turns what would otherwise be a startup panic into, at worst, a
+/// recoverable [`AppError`] that `setup` can show as a warning instead of a
+/// crash.
+pub async fn init_db_with_recovery(database_url: &str) -> Result<SqlitePool, AppError> {
+    match init_db(database_url).await {
+        Ok(pool) => Ok(pool),
+        Err(err) => {
+            log::warn!(
+                "database at '{database_url}' failed to initialize ({err}); attempting recovery"
+            );
+            if let Some(path) = db_file_path(database_url) {
+                if path.exists() {
+                    let backup = backup_path(&path);
+                    std::fs::rename(&path, &backup).map_err(|e| {
+                        AppError::Internal(format!(
+                            "failed to back up corrupt database to {}: {e}",
+                            backup.display()
+                        ))
+                    })?;
+                    log::warn!("moved corrupt database to {}", backup.display());
+                }
+            }
+            init_db(database_url).await
+        }
+    }
+}
+
+/// Extracts the filesystem path from a `sqlite:` connection url, or `None`
+/// for in-memory databases which have nothing to back up.
+fn db_file_path(database_url: &str) -> Option<PathBuf> {
+    if database_url.contains(":memory:") {
+        return None;
+    }
+    let path = database_url
+        .strip_prefix("sqlite://")
+        .or_else(|| database_url.strip_prefix("sqlite:"))
+        .unwrap_or(database_url);
+    Some(PathBuf::from(path))
+}
+
+fn backup_path(original: &Path) -> PathBuf {
+    let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    let mut backup = original.as_os_str().to_os_string();
+    backup.push(format!(".corrupt-{timestamp}"));
+    PathBuf::from(backup)
+}
+
+/// Flushes the WAL and closes `pool` for a clean shutdown (e.g. wired to
+/// Tauri's `RunEvent::Exit`), so a killed process doesn't leave buffered
+/// writes sitting in an uncheckpointed WAL file. Idempotent — closing an
+/// already-closed pool is a no-op, and [`SqlitePool::close`] itself already
+/// tolerates repeat calls, so this is safe to call from more than one exit
+/// path without coordination.
+pub async fn close_db(pool: &SqlitePool) {
+    if pool.is_closed() {
+        return;
+    }
+    if let Err(err) = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(pool)
+        .await
+    {
+        log::warn!("WAL checkpoint failed during shutdown: {err}");
+    }
+    pool.close().await;
+}
+
+/// Test helper that initializes a fresh, independent database in its own
+/// temp file. Each call gets its own pool, so tests no longer need
+/// globally-unique data to avoid colliding with unrelated tests.
+#[cfg(test)]
+pub async fn init_db_for_test() -> SqlitePool {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("feast-test-{}.db", uuid::Uuid::new_v4()));
+    let url = format!("sqlite://{}", path.display());
+    init_db(&url)
+        .await
+        .expect("failed to initialize test database")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn init_db_with_recovery_backs_up_and_recreates_a_corrupt_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("feast-corrupt-test-{}.db", Uuid::new_v4()));
+        std::fs::write(&path, b"this is not a sqlite database").unwrap();
+        let url = format!("sqlite://{}", path.display());
+
+        let pool = init_db_with_recovery(&url).await.unwrap();
+
+        let recipe_count: i64 = sqlx::query_scalar("SELECT count(*) FROM recipes")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(recipe_count, 0);
+
+        let backups: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name().to_string_lossy().starts_with(&format!(
+                    "{}.corrupt-",
+                    path.file_name().unwrap().to_string_lossy()
+                ))
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+
+        std::fs::remove_file(&path).ok();
+        for backup in backups {
+            std::fs::remove_file(backup.path()).ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn close_db_is_idempotent_and_leaves_the_pool_unusable() {
+        let pool = init_db_for_test().await;
+
+        close_db(&pool).await;
+        close_db(&pool).await;
+
+        let result = sqlx::query_scalar::<_, i64>("SELECT count(*) FROM recipes")
+            .fetch_one(&pool)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn two_pools_on_separate_files_do_not_see_each_others_writes() {
+        let pool_a = init_db_for_test().await;
+        let pool_b = init_db_for_test().await;
+
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('pool-a-marker', 'present')")
+            .execute(&pool_a)
+            .await
+            .unwrap();
+
+        let seen_in_a: Option<String> =
+            sqlx::query_scalar("SELECT value FROM settings WHERE key = 'pool-a-marker'")
+                .fetch_optional(&pool_a)
+                .await
+                .unwrap();
+        assert_eq!(seen_in_a, Some("present".to_string()));
+
+        let seen_in_b: Option<String> =
+            sqlx::query_scalar("SELECT value FROM settings WHERE key = 'pool-a-marker'")
+                .fetch_optional(&pool_b)
+                .await
+                .unwrap();
+        assert_eq!(seen_in_b, None);
+    }
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+
+use crate::db::settings::get_shopping_item_retention_days;
+use crate::error::AppError;
+
+/// SQLite's `datetime('now')` default (used for `created_at`/`deleted_at`
+/// throughout this schema) formats as `"YYYY-MM-DD HH:MM:SS"`, which sorts
+/// and compares lexicographically the same as chronologically — formatting
+/// the cutoff the same way lets it bind straight into a `<` comparison.
+const SQLITE_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The point in time before which a soft-deleted row is old enough to
+/// purge: `retention_days` before `now`. Extracted on its own so the
+/// retention math can be tested without a database.
+pub fn compute_purge_cutoff(now: DateTime<Utc>, retention_days: i64) -> DateTime<Utc> {
+    now - Duration::days(retention_days)
+}
+
+/// Permanently removes manual shopping items that were soft-deleted (via
+/// [`crate::db::manual_items::soft_delete_shopping_item`]) longer ago than
+/// the configured `shopping_item_retention_days` setting (default
+/// [`crate::db::settings::DEFAULT_SHOPPING_ITEM_RETENTION_DAYS`] days).
+/// Run on every [`crate::db::pool::init_db`] call so the table doesn't grow
+/// unbounded with items nobody will restore. Returns the number of rows
+/// removed.
+pub async fn purge_old_soft_deleted_shopping_items(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> Result<u64, AppError> {
+    let retention_days = get_shopping_item_retention_days(pool).await?;
+    let cutoff = compute_purge_cutoff(now, retention_days)
+        .format(SQLITE_DATETIME_FORMAT)
+        .to_string();
+
+    let result = sqlx::query(
+        "DELETE FROM manual_shopping_items WHERE deleted_at IS NOT NULL AND deleted_at < ?",
+    )
+    .bind(&cutoff)
+    .execute(pool)
+    .await?;
+
+    let removed = result.rows_affected();
+    if removed > 0 {
+        log::info!(
+            "purged {removed} soft-deleted shopping item(s) older than {retention_days} day(s)"
+        );
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::manual_items::{create_manual_item, soft_delete_shopping_item};
+    use crate::db::pool::init_db_for_test;
+    use crate::db::settings::set_setting;
+    use crate::models::ManualShoppingItemInput;
+    use chrono::TimeZone;
+
+    #[test]
+    fn cutoff_is_retention_days_before_now() {
+        let now = Utc.with_ymd_and_hms(2026, 8, 9, 12, 0, 0).unwrap();
+
+        let cutoff = compute_purge_cutoff(now, 30);
+
+        assert_eq!(cutoff, Utc.with_ymd_and_hms(2026, 7, 10, 12, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn purge_removes_old_soft_deleted_rows_but_keeps_recent_ones() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, "shopping_item_retention_days", "30")
+            .await
+            .unwrap();
+
+        let old_item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-06-01".to_string(),
+                name: "purge-test Stale Bread".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &old_item.id)
+            .await
+            .unwrap();
+        // Backdate past the retention window directly, since
+        // `soft_delete_shopping_item` always stamps the current time.
+        sqlx::query(
+            "UPDATE manual_shopping_items SET deleted_at = '2026-01-01 00:00:00' WHERE id = ?",
+        )
+        .bind(&old_item.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let recent_item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-01".to_string(),
+                name: "purge-test Fresh Bread".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+        soft_delete_shopping_item(&pool, &recent_item.id)
+            .await
+            .unwrap();
+
+        let kept_item = create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-08-01".to_string(),
+                name: "purge-test Untouched Bread".to_string(),
+                quantity: None,
+                unit: None,
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let removed = purge_old_soft_deleted_shopping_items(&pool, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM manual_shopping_items")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert!(!remaining.contains(&old_item.id));
+        assert!(remaining.contains(&recent_item.id));
+        assert!(remaining.contains(&kept_item.id));
+    }
+}
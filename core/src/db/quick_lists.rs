@@ -0,0 +1,458 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::db::manual_items::add_shopping_item;
+use crate::error::AppError;
+use crate::models::{
+    ManualShoppingItem, ManualShoppingItemInput, QuickList, QuickListInput, QuickListItem,
+    QuickListItemInput, QuickListWithItems,
+};
+
+pub async fn create_quick_list(
+    pool: &SqlitePool,
+    input: QuickListInput,
+) -> Result<QuickList, AppError> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO quick_lists (id, name) VALUES (?, ?)")
+        .bind(&id)
+        .bind(&input.name)
+        .execute(pool)
+        .await?;
+
+    get_quick_list_by_id(pool, &id).await?.ok_or_else(|| {
+        AppError::Internal("quick list vanished immediately after insert".to_string())
+    })
+}
+
+pub async fn get_quick_list_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<QuickList>, AppError> {
+    let list = sqlx::query_as::<_, QuickList>("SELECT * FROM quick_lists WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(list)
+}
+
+pub async fn add_quick_list_item(
+    pool: &SqlitePool,
+    input: QuickListItemInput,
+) -> Result<QuickListItem, AppError> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO quick_list_items (id, quick_list_id, name, quantity, unit, category) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&input.quick_list_id)
+    .bind(&input.name)
+    .bind(input.quantity)
+    .bind(&input.unit)
+    .bind(&input.category)
+    .execute(pool)
+    .await?;
+
+    get_quick_list_item_by_id(pool, &id).await?.ok_or_else(|| {
+        AppError::Internal("quick list item vanished immediately after insert".to_string())
+    })
+}
+
+pub async fn get_quick_list_item_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<QuickListItem>, AppError> {
+    let item = sqlx::query_as::<_, QuickListItem>("SELECT * FROM quick_list_items WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(item)
+}
+
+/// Items in `quick_list_id`, ordered by category (uncategorized last) then
+/// name, so copies into a shopping list preserve a sensible grouping.
+async fn list_quick_list_items(
+    pool: &SqlitePool,
+    quick_list_id: &str,
+) -> Result<Vec<QuickListItem>, AppError> {
+    let items = sqlx::query_as::<_, QuickListItem>(
+        "SELECT * FROM quick_list_items WHERE quick_list_id = ? \
+         ORDER BY category IS NULL, category, name",
+    )
+    .bind(quick_list_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(items)
+}
+
+/// Copies every item of `quick_list_id` into the `week_start` shopping
+/// list, in category order. With `merge` on, an item that matches an
+/// existing unchecked row with a compatible unit has its quantity combined
+/// via [`add_shopping_item`]'s aggregation instead of duplicating the row;
+/// with `merge` off, every item is inserted as a new row.
+pub async fn add_quick_list_to_shopping(
+    pool: &SqlitePool,
+    quick_list_id: &str,
+    week_start: &str,
+    merge: bool,
+) -> Result<Vec<ManualShoppingItem>, AppError> {
+    let items = list_quick_list_items(pool, quick_list_id).await?;
+    let mut created = Vec::with_capacity(items.len());
+    for item in items {
+        let shopping_item = add_shopping_item(
+            pool,
+            ManualShoppingItemInput {
+                week_start: week_start.to_string(),
+                name: item.name,
+                quantity: item.quantity,
+                unit: item.unit,
+                category: item.category,
+            },
+            merge,
+        )
+        .await?;
+        created.push(shopping_item);
+    }
+
+    sqlx::query("UPDATE quick_lists SET last_used_at = datetime('now') WHERE id = ?")
+        .bind(quick_list_id)
+        .execute(pool)
+        .await?;
+
+    Ok(created)
+}
+
+/// Quick lists that either have never been copied into a shopping list, or
+/// haven't been in more than `older_than_days` days, for a "clean up stale
+/// templates" view.
+pub async fn get_stale_quick_lists(
+    pool: &SqlitePool,
+    older_than_days: i64,
+) -> Result<Vec<QuickList>, AppError> {
+    let lists = sqlx::query_as::<_, QuickList>(
+        "SELECT * FROM quick_lists \
+         WHERE last_used_at IS NULL \
+            OR last_used_at <= datetime('now', ? || ' days') \
+         ORDER BY last_used_at IS NOT NULL, last_used_at, name",
+    )
+    .bind(-older_than_days)
+    .fetch_all(pool)
+    .await?;
+    Ok(lists)
+}
+
+/// Clones `id` into a brand new quick list named "<name> (Copy)", along
+/// with every one of its items under fresh ids, within a transaction so a
+/// crash partway through can't leave an orphaned half-copied list. The
+/// copy shares no rows with the original, so editing it afterward — even
+/// deleting it entirely — can never affect the source list.
+pub async fn duplicate_quick_list(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<QuickListWithItems, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let original = sqlx::query_as::<_, QuickList>("SELECT * FROM quick_lists WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("quick list '{id}' does not exist")))?;
+
+    let items = sqlx::query_as::<_, QuickListItem>(
+        "SELECT * FROM quick_list_items WHERE quick_list_id = ? \
+         ORDER BY category IS NULL, category, name",
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let new_list_id = Uuid::new_v4().to_string();
+    let new_name = format!("{} (Copy)", original.name);
+    sqlx::query("INSERT INTO quick_lists (id, name) VALUES (?, ?)")
+        .bind(&new_list_id)
+        .bind(&new_name)
+        .execute(&mut *tx)
+        .await?;
+
+    for item in items {
+        sqlx::query(
+            "INSERT INTO quick_list_items (id, quick_list_id, name, quantity, unit, category) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&new_list_id)
+        .bind(&item.name)
+        .bind(item.quantity)
+        .bind(&item.unit)
+        .bind(&item.category)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let new_list = sqlx::query_as::<_, QuickList>("SELECT * FROM quick_lists WHERE id = ?")
+        .bind(&new_list_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let new_items = sqlx::query_as::<_, QuickListItem>(
+        "SELECT * FROM quick_list_items WHERE quick_list_id = ? \
+         ORDER BY category IS NULL, category, name",
+    )
+    .bind(&new_list_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(QuickListWithItems {
+        list: new_list,
+        items: new_items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::manual_items::get_manual_item_by_id;
+    use crate::db::pool::init_db_for_test;
+
+    async fn seed_list(
+        pool: &SqlitePool,
+        name: &str,
+        items: &[(&str, Option<f64>, Option<&str>)],
+    ) -> QuickList {
+        let list = create_quick_list(
+            pool,
+            QuickListInput {
+                name: name.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        for (item_name, quantity, unit) in items {
+            add_quick_list_item(
+                pool,
+                QuickListItemInput {
+                    quick_list_id: list.id.clone(),
+                    name: item_name.to_string(),
+                    quantity: *quantity,
+                    unit: unit.map(|u| u.to_string()),
+                    category: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        list
+    }
+
+    #[tokio::test]
+    async fn copying_into_an_empty_list_adds_items_as_is() {
+        let pool = init_db_for_test().await;
+        let week = "2026-07-06";
+        let list = seed_list(
+            &pool,
+            "quicklist-test empty target",
+            &[
+                ("quicklist-test flour", Some(2.0), Some("cups")),
+                ("quicklist-test eggs", Some(3.0), None),
+            ],
+        )
+        .await;
+
+        let created = add_quick_list_to_shopping(&pool, &list.id, week, true)
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].name, "quicklist-test eggs");
+        assert_eq!(created[0].quantity, Some(3.0));
+        assert_eq!(created[1].name, "quicklist-test flour");
+        assert_eq!(created[1].quantity, Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn copying_with_merge_on_combines_matching_item() {
+        let pool = init_db_for_test().await;
+        let week = "2026-07-13";
+
+        let existing = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "quicklist-test merge flour".to_string(),
+                quantity: Some(1.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        let list = seed_list(
+            &pool,
+            "quicklist-test merge source",
+            &[("quicklist-test merge flour", Some(1.0), Some("cups"))],
+        )
+        .await;
+
+        let created = add_quick_list_to_shopping(&pool, &list.id, week, true)
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id, existing.id);
+        assert_eq!(created[0].quantity, Some(2.0));
+
+        let row = get_manual_item_by_id(&pool, &existing.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.quantity, Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn copying_with_merge_off_duplicates_matching_item() {
+        let pool = init_db_for_test().await;
+        let week = "2026-07-20";
+
+        let existing = add_shopping_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "quicklist-test no-merge flour".to_string(),
+                quantity: Some(1.0),
+                unit: Some("cups".to_string()),
+                category: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        let list = seed_list(
+            &pool,
+            "quicklist-test no-merge source",
+            &[("quicklist-test no-merge flour", Some(1.0), Some("cups"))],
+        )
+        .await;
+
+        let created = add_quick_list_to_shopping(&pool, &list.id, week, false)
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 1);
+        assert_ne!(created[0].id, existing.id);
+        assert_eq!(created[0].quantity, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn duplicating_a_quick_list_copies_items_independently_of_the_original() {
+        let pool = init_db_for_test().await;
+        let original = seed_list(
+            &pool,
+            "quicklist-test weekly staples",
+            &[
+                ("quicklist-test dup milk", Some(1.0), Some("gallon")),
+                ("quicklist-test dup eggs", Some(12.0), None),
+            ],
+        )
+        .await;
+        let original_items = list_quick_list_items(&pool, &original.id).await.unwrap();
+        let original_milk = original_items
+            .iter()
+            .find(|i| i.name == "quicklist-test dup milk")
+            .unwrap();
+
+        let copy = duplicate_quick_list(&pool, &original.id).await.unwrap();
+
+        assert_eq!(copy.list.name, "quicklist-test weekly staples (Copy)");
+        assert_ne!(copy.list.id, original.id);
+        assert_eq!(copy.items.len(), 2);
+        assert!(copy.items.iter().all(|i| i.quick_list_id == copy.list.id));
+
+        let copied_milk = copy
+            .items
+            .iter()
+            .find(|i| i.name == "quicklist-test dup milk")
+            .unwrap();
+        assert_ne!(copied_milk.id, original_milk.id);
+
+        sqlx::query("UPDATE quick_list_items SET quantity = ? WHERE id = ?")
+            .bind(5.0)
+            .bind(&copied_milk.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let unchanged_original = get_quick_list_item_by_id(&pool, &original_milk.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged_original.quantity, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn stale_lists_excludes_a_freshly_used_list() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-07";
+        let list = seed_list(
+            &pool,
+            "stale-test freshly used",
+            &[("stale-test fresh item", None, None)],
+        )
+        .await;
+
+        add_quick_list_to_shopping(&pool, &list.id, week, true)
+            .await
+            .unwrap();
+
+        let stale = get_stale_quick_lists(&pool, 30).await.unwrap();
+
+        assert!(!stale.iter().any(|l| l.id == list.id));
+    }
+
+    #[tokio::test]
+    async fn stale_lists_includes_a_never_used_list() {
+        let pool = init_db_for_test().await;
+        let list = seed_list(
+            &pool,
+            "stale-test never used",
+            &[("stale-test never-used item", None, None)],
+        )
+        .await;
+
+        let stale = get_stale_quick_lists(&pool, 30).await.unwrap();
+
+        assert!(stale.iter().any(|l| l.id == list.id));
+    }
+
+    #[tokio::test]
+    async fn stale_lists_includes_an_old_used_list_under_a_recent_cutoff() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-14";
+        let list = seed_list(
+            &pool,
+            "stale-test old used",
+            &[("stale-test old item", None, None)],
+        )
+        .await;
+        add_quick_list_to_shopping(&pool, &list.id, week, true)
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE quick_lists SET last_used_at = datetime('now', '-60 days') WHERE id = ?",
+        )
+        .bind(&list.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let stale = get_stale_quick_lists(&pool, 30).await.unwrap();
+
+        assert!(stale.iter().any(|l| l.id == list.id));
+    }
+}
@@ -0,0 +1,1166 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{
+    Recipe, RecipeIngredientExport, RecipeInput, RecipeQualityIssue, DIFFICULTIES,
+};
+
+/// Rejects any `difficulty` outside [`DIFFICULTIES`] — `None` (unrated) is
+/// always fine.
+pub(crate) fn validate_difficulty(difficulty: &Option<String>) -> Result<(), AppError> {
+    match difficulty {
+        Some(value) if !DIFFICULTIES.contains(&value.as_str()) => Err(AppError::Validation(
+            format!("'{value}' is not a valid difficulty (expected one of {DIFFICULTIES:?})"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects any `rating` outside 1-5 — `None` (clearing the rating) is
+/// always fine.
+fn validate_user_rating(rating: &Option<i64>) -> Result<(), AppError> {
+    match rating {
+        Some(value) if !(1..=5).contains(value) => Err(AppError::Validation(format!(
+            "rating must be between 1 and 5, got {value}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Sets the user's own 1-5 star rating on a recipe, independent of any
+/// imported `rating_value`, or clears it with `None`. Returns
+/// [`AppError::NotFound`] if `id` doesn't exist.
+pub async fn set_user_rating(
+    pool: &SqlitePool,
+    id: &str,
+    rating: Option<i64>,
+) -> Result<Recipe, AppError> {
+    validate_user_rating(&rating)?;
+    let result = sqlx::query("UPDATE recipes SET user_rating = ? WHERE id = ?")
+        .bind(rating)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("recipe '{id}' does not exist")));
+    }
+    get_recipe_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::Internal("recipe vanished immediately after rating".to_string()))
+}
+
+/// The top `limit` recipes by `user_rating`, highest first, for a "my
+/// favorites" view. Recipes the user hasn't rated are excluded rather than
+/// sorted to either end, since `NULL` isn't a rating. Ties break by name.
+pub async fn get_top_rated_recipes(pool: &SqlitePool, limit: i64) -> Result<Vec<Recipe>, AppError> {
+    let recipes = sqlx::query_as::<_, Recipe>(
+        "SELECT * FROM recipes WHERE user_rating IS NOT NULL \
+         ORDER BY user_rating DESC, name LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(recipes)
+}
+
+pub async fn create_recipe(pool: &SqlitePool, input: RecipeInput) -> Result<Recipe, AppError> {
+    validate_difficulty(&input.difficulty)?;
+    let id = Uuid::new_v4().to_string();
+    let instructions = serde_json::to_string(&input.instructions)
+        .map_err(|e| AppError::Internal(format!("failed to serialize instructions: {e}")))?;
+
+    sqlx::query(
+        "INSERT INTO recipes (id, name, description, servings, yield_unit, prep_time, \
+         cook_time, instructions, image_path, source_url, notes, rating_value, rating_count, \
+         difficulty, yield_notes) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&input.name)
+    .bind(&input.description)
+    .bind(input.servings)
+    .bind(&input.yield_unit)
+    .bind(input.prep_time)
+    .bind(input.cook_time)
+    .bind(&instructions)
+    .bind(&input.image_path)
+    .bind(&input.source_url)
+    .bind(&input.notes)
+    .bind(input.rating_value)
+    .bind(input.rating_count)
+    .bind(&input.difficulty)
+    .bind(&input.yield_notes)
+    .execute(pool)
+    .await?;
+
+    get_recipe_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::Internal("recipe vanished immediately after insert".to_string()))
+}
+
+/// Overwrites every editable field of `id` with `input`, `ingredients`, and
+/// `tags`, for a full edit-recipe form save — unlike [`create_recipe`],
+/// this replaces the existing row's ingredients and tags outright rather
+/// than appending to them, since the caller is expected to submit the
+/// complete, current set of both rather than a delta. Returns
+/// [`AppError::NotFound`] if `id` doesn't exist.
+pub async fn update_recipe(
+    pool: &SqlitePool,
+    id: &str,
+    input: RecipeInput,
+    ingredients: &[RecipeIngredientExport],
+    tags: &[String],
+) -> Result<Recipe, AppError> {
+    validate_difficulty(&input.difficulty)?;
+    let instructions = serde_json::to_string(&input.instructions)
+        .map_err(|e| AppError::Internal(format!("failed to serialize instructions: {e}")))?;
+
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query(
+        "UPDATE recipes SET name = ?, description = ?, servings = ?, yield_unit = ?, \
+         prep_time = ?, cook_time = ?, instructions = ?, image_path = ?, source_url = ?, \
+         notes = ?, rating_value = ?, rating_count = ?, difficulty = ?, yield_notes = ?, \
+         updated_at = datetime('now') \
+         WHERE id = ?",
+    )
+    .bind(&input.name)
+    .bind(&input.description)
+    .bind(input.servings)
+    .bind(&input.yield_unit)
+    .bind(input.prep_time)
+    .bind(input.cook_time)
+    .bind(&instructions)
+    .bind(&input.image_path)
+    .bind(&input.source_url)
+    .bind(&input.notes)
+    .bind(input.rating_value)
+    .bind(input.rating_count)
+    .bind(&input.difficulty)
+    .bind(&input.yield_notes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("recipe '{id}' does not exist")));
+    }
+
+    sqlx::query("DELETE FROM recipe_ingredients WHERE recipe_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM recipe_tags WHERE recipe_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    for ingredient in ingredients {
+        add_recipe_ingredient(pool, id, ingredient, None).await?;
+    }
+    for tag in tags {
+        add_recipe_tag(pool, id, tag).await?;
+    }
+
+    get_recipe_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::Internal("recipe vanished immediately after update".to_string()))
+}
+
+pub async fn get_recipe_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Recipe>, AppError> {
+    let recipe = sqlx::query_as::<_, Recipe>("SELECT * FROM recipes WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(recipe)
+}
+
+pub async fn recipe_exists(pool: &SqlitePool, id: &str) -> Result<bool, AppError> {
+    let exists: Option<i64> = sqlx::query_scalar("SELECT 1 FROM recipes WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(exists.is_some())
+}
+
+/// Clears a recipe's `image_path` without touching anything else, for a
+/// "remove image" action that would otherwise require a full
+/// [`update_recipe`] (which also re-writes ingredients and tags). Images
+/// are stored as the URL they were imported from rather than a downloaded
+/// local file, so there's nothing on disk to clean up here.
+pub async fn clear_image(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    let result = sqlx::query(
+        "UPDATE recipes SET image_path = NULL, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("recipe '{id}' does not exist")));
+    }
+    Ok(())
+}
+
+pub async fn delete_recipe(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM recipes WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("recipe '{id}' does not exist")));
+    }
+    Ok(())
+}
+
+/// Every recipe, ordered by name, for bundle export/backup. Intended to be
+/// paired with [`get_recipe_ingredients`] and [`get_recipe_tags`] per recipe
+/// rather than one giant join, so a large library doesn't need every row
+/// materialized at once.
+pub async fn list_all_recipes(pool: &SqlitePool) -> Result<Vec<Recipe>, AppError> {
+    let recipes = sqlx::query_as::<_, Recipe>("SELECT * FROM recipes ORDER BY name")
+        .fetch_all(pool)
+        .await?;
+    Ok(recipes)
+}
+
+pub async fn recipe_exists_by_source_url(
+    pool: &SqlitePool,
+    source_url: &str,
+) -> Result<bool, AppError> {
+    let exists: Option<i64> = sqlx::query_scalar("SELECT 1 FROM recipes WHERE source_url = ?")
+        .bind(source_url)
+        .fetch_optional(pool)
+        .await?;
+    Ok(exists.is_some())
+}
+
+/// Strips the URL fragment and any trailing slash, so lookups tolerate
+/// `"https://example.com/r"` and `"https://example.com/r/#top"` referring
+/// to the same page.
+fn normalize_source_url(url: &str) -> String {
+    url.split('#')
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Looks a recipe up by `source_url`, for dedup UIs and "open the recipe I
+/// imported from this link". Tries [`recipe_exists_by_source_url`]'s exact
+/// match first, then falls back to comparing [`normalize_source_url`] of
+/// every imported recipe's `source_url`, so a trailing slash or `#fragment`
+/// difference between the stored value and `url` doesn't cause a miss.
+pub async fn get_recipe_by_source_url(
+    pool: &SqlitePool,
+    url: &str,
+) -> Result<Option<Recipe>, AppError> {
+    if let Some(recipe) = sqlx::query_as::<_, Recipe>("SELECT * FROM recipes WHERE source_url = ?")
+        .bind(url)
+        .fetch_optional(pool)
+        .await?
+    {
+        return Ok(Some(recipe));
+    }
+
+    let normalized_target = normalize_source_url(url);
+    let candidates =
+        sqlx::query_as::<_, Recipe>("SELECT * FROM recipes WHERE source_url IS NOT NULL")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(candidates.into_iter().find(|recipe| {
+        recipe
+            .source_url
+            .as_deref()
+            .is_some_and(|existing| normalize_source_url(existing) == normalized_target)
+    }))
+}
+
+pub async fn get_recipe_ingredients(
+    pool: &SqlitePool,
+    recipe_id: &str,
+) -> Result<Vec<RecipeIngredientExport>, AppError> {
+    let ingredients = sqlx::query_as::<_, RecipeIngredientExport>(
+        "SELECT i.name AS name, ri.quantity AS quantity, ri.unit AS unit, \
+                ri.notes AS notes, ri.sort_order AS sort_order \
+         FROM recipe_ingredients ri \
+         JOIN ingredients i ON i.id = ri.ingredient_id \
+         WHERE ri.recipe_id = ? \
+         ORDER BY ri.sort_order",
+    )
+    .bind(recipe_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(ingredients)
+}
+
+/// Recipes pulled in from a URL, i.e. `source_url IS NOT NULL`, most
+/// recently imported first — for a "Your imports" view.
+pub async fn get_imported_recipes(pool: &SqlitePool) -> Result<Vec<Recipe>, AppError> {
+    let recipes = sqlx::query_as::<_, Recipe>(
+        "SELECT * FROM recipes WHERE source_url IS NOT NULL ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(recipes)
+}
+
+/// Recipes with no `source_url`, i.e. entered by hand rather than imported,
+/// most recently created first.
+pub async fn get_manual_recipes(pool: &SqlitePool) -> Result<Vec<Recipe>, AppError> {
+    let recipes = sqlx::query_as::<_, Recipe>(
+        "SELECT * FROM recipes WHERE source_url IS NULL ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(recipes)
+}
+
+/// The top `limit` recipes by `rating_value`, highest first, for a "best
+/// rated" view. Recipes with no rating (never imported with one, or
+/// hand-entered) are excluded rather than sorted to either end, since
+/// `NULL` isn't a rating. Ties break by `rating_count` descending, so a
+/// 4.8-star recipe with 500 reviews outranks one with 2.
+pub async fn get_recipes_sorted_by_rating(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<Recipe>, AppError> {
+    let recipes = sqlx::query_as::<_, Recipe>(
+        "SELECT * FROM recipes WHERE rating_value IS NOT NULL \
+         ORDER BY rating_value DESC, rating_count DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(recipes)
+}
+
+pub async fn get_recipe_tags(pool: &SqlitePool, recipe_id: &str) -> Result<Vec<String>, AppError> {
+    let tags = sqlx::query_scalar(
+        "SELECT t.name FROM recipe_tags rt JOIN tags t ON t.id = rt.tag_id \
+         WHERE rt.recipe_id = ? ORDER BY t.name",
+    )
+    .bind(recipe_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(tags)
+}
+
+/// Finds recipes missing instructions, ingredients, or an image, for a
+/// data-quality report ("3 recipes need attention") after lenient imports
+/// or sloppy manual edits.
+pub async fn find_incomplete_recipes(
+    pool: &SqlitePool,
+) -> Result<Vec<RecipeQualityIssue>, AppError> {
+    let issues = sqlx::query_as::<_, RecipeQualityIssue>(
+        "SELECT r.id AS recipe_id, r.name AS name, \
+                (r.instructions = '[]' OR r.instructions = '') AS missing_instructions, \
+                (count(ri.id) = 0) AS missing_ingredients, \
+                (r.image_path IS NULL) AS missing_image \
+         FROM recipes r \
+         LEFT JOIN recipe_ingredients ri ON ri.recipe_id = r.id \
+         GROUP BY r.id \
+         HAVING missing_instructions OR missing_ingredients OR missing_image \
+         ORDER BY r.name",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(issues)
+}
+
+/// Attaches `ingredient` to `recipe_id`, creating the ingredient row first
+/// if no ingredient with that name exists yet. `inferred_category` is the
+/// category the caller's own parsing already determined for this
+/// ingredient, if any; when absent, [`settings::resolve_import_category`]
+/// is used instead so a freshly-created ingredient still gets a sensible
+/// category rather than `NULL`.
+pub async fn add_recipe_ingredient(
+    pool: &SqlitePool,
+    recipe_id: &str,
+    ingredient: &RecipeIngredientExport,
+    inferred_category: Option<&str>,
+) -> Result<(), AppError> {
+    let ingredient_id: Option<String> =
+        sqlx::query_scalar("SELECT id FROM ingredients WHERE name = ?")
+            .bind(&ingredient.name)
+            .fetch_optional(pool)
+            .await?;
+
+    let ingredient_id = match ingredient_id {
+        Some(id) => id,
+        None => {
+            let category =
+                crate::db::settings::resolve_import_category(pool, inferred_category).await?;
+            let id = Uuid::new_v4().to_string();
+            sqlx::query("INSERT INTO ingredients (id, name, category) VALUES (?, ?, ?)")
+                .bind(&id)
+                .bind(&ingredient.name)
+                .bind(&category)
+                .execute(pool)
+                .await?;
+            id
+        }
+    };
+
+    sqlx::query(
+        "INSERT INTO recipe_ingredients (id, recipe_id, ingredient_id, quantity, unit, notes, sort_order) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(recipe_id)
+    .bind(&ingredient_id)
+    .bind(ingredient.quantity)
+    .bind(&ingredient.unit)
+    .bind(&ingredient.notes)
+    .bind(ingredient.sort_order)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Attaches `tag_name` to `recipe_id`, creating the tag row first if no tag
+/// with that name exists yet.
+pub async fn add_recipe_tag(
+    pool: &SqlitePool,
+    recipe_id: &str,
+    tag_name: &str,
+) -> Result<(), AppError> {
+    let tag_id: Option<String> = sqlx::query_scalar("SELECT id FROM tags WHERE name = ?")
+        .bind(tag_name)
+        .fetch_optional(pool)
+        .await?;
+
+    let tag_id = match tag_id {
+        Some(id) => id,
+        None => {
+            let id = Uuid::new_v4().to_string();
+            sqlx::query("INSERT INTO tags (id, name) VALUES (?, ?)")
+                .bind(&id)
+                .bind(tag_name)
+                .execute(pool)
+                .await?;
+            id
+        }
+    };
+
+    sqlx::query("INSERT OR IGNORE INTO recipe_tags (recipe_id, tag_id) VALUES (?, ?)")
+        .bind(recipe_id)
+        .bind(&tag_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Renames `old` to `new` everywhere, for fixing typos ("dinnner" ->
+/// "dinner") without editing every recipe that carries the tag. If `new`
+/// already exists as its own tag, `old`'s recipes are repointed at it
+/// instead and the now-orphaned `old` row is deleted, so the rename never
+/// produces two rows for what's now the same tag name. Runs in a
+/// transaction so a crash partway through can't leave `recipe_tags`
+/// pointing at a deleted tag.
+pub async fn rename_tag(pool: &SqlitePool, old: &str, new: &str) -> Result<(), AppError> {
+    let old = old.trim();
+    let new = new.trim();
+
+    let mut tx = pool.begin().await?;
+
+    let old_id: Option<String> = sqlx::query_scalar("SELECT id FROM tags WHERE name = ?")
+        .bind(old)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let old_id = old_id.ok_or_else(|| AppError::NotFound(format!("tag '{old}' does not exist")))?;
+
+    let existing_new_id: Option<String> = sqlx::query_scalar("SELECT id FROM tags WHERE name = ?")
+        .bind(new)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    match existing_new_id {
+        Some(new_id) if new_id != old_id => {
+            sqlx::query(
+                "INSERT OR IGNORE INTO recipe_tags (recipe_id, tag_id) \
+                 SELECT recipe_id, ? FROM recipe_tags WHERE tag_id = ?",
+            )
+            .bind(&new_id)
+            .bind(&old_id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("DELETE FROM recipe_tags WHERE tag_id = ?")
+                .bind(&old_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM tags WHERE id = ?")
+                .bind(&old_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        _ => {
+            sqlx::query("UPDATE tags SET name = ? WHERE id = ?")
+                .bind(new)
+                .bind(&old_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Every tag paired with how many recipes carry it, sorted by count
+/// descending (ties broken alphabetically), for a tag cloud sized by
+/// frequency. The join with `recipe_tags` means a tag with no recipes left
+/// (e.g. the last recipe carrying it was deleted) simply doesn't appear,
+/// rather than showing up at weight zero.
+pub async fn get_tag_histogram(pool: &SqlitePool) -> Result<Vec<(String, i64)>, AppError> {
+    let histogram = sqlx::query_as(
+        "SELECT t.name, count(rt.recipe_id) AS recipe_count \
+         FROM tags t \
+         JOIN recipe_tags rt ON rt.tag_id = t.id \
+         GROUP BY t.id \
+         ORDER BY recipe_count DESC, t.name",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+    use uuid::Uuid;
+
+    fn sample_recipe_input(name: &str) -> RecipeInput {
+        RecipeInput {
+            name: name.to_string(),
+            description: None,
+            servings: 2,
+            yield_unit: None,
+            prep_time: None,
+            cook_time: None,
+            instructions: vec![],
+            image_path: None,
+            source_url: None,
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_recipe_persists_and_reads_back_difficulty_and_yield_notes() {
+        let pool = init_db_for_test().await;
+        let mut input = sample_recipe_input("Difficulty Chili");
+        input.difficulty = Some("Medium".to_string());
+        input.yield_notes = Some("makes 2 loaves, freezes well".to_string());
+
+        let created = create_recipe(&pool, input).await.unwrap();
+        let fetched = get_recipe_by_id(&pool, &created.id).await.unwrap().unwrap();
+
+        assert_eq!(fetched.difficulty, Some("Medium".to_string()));
+        assert_eq!(
+            fetched.yield_notes,
+            Some("makes 2 loaves, freezes well".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn create_recipe_rejects_an_invalid_difficulty() {
+        let pool = init_db_for_test().await;
+        let mut input = sample_recipe_input("Invalid Difficulty Chili");
+        input.difficulty = Some("Impossible".to_string());
+
+        let result = create_recipe(&pool, input).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_recipe_replaces_fields_ingredients_and_tags() {
+        let pool = init_db_for_test().await;
+        let created = create_recipe(&pool, sample_recipe_input("Update Me Chili"))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &created.id,
+            &RecipeIngredientExport {
+                name: "update-me-original-bean".to_string(),
+                quantity: 1.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        add_recipe_tag(&pool, &created.id, "update-me-original-tag")
+            .await
+            .unwrap();
+
+        let mut input = sample_recipe_input("Updated Chili");
+        input.difficulty = Some("Hard".to_string());
+        input.yield_notes = Some("doubles well".to_string());
+        let updated = update_recipe(
+            &pool,
+            &created.id,
+            input,
+            &[RecipeIngredientExport {
+                name: "update-me-new-bean".to_string(),
+                quantity: 2.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            }],
+            &["update-me-new-tag".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated.name, "Updated Chili");
+        assert_eq!(updated.difficulty, Some("Hard".to_string()));
+        assert_eq!(updated.yield_notes, Some("doubles well".to_string()));
+
+        let ingredients = get_recipe_ingredients(&pool, &created.id).await.unwrap();
+        assert_eq!(ingredients.len(), 1);
+        assert_eq!(ingredients[0].name, "update-me-new-bean");
+        let tags = get_recipe_tags(&pool, &created.id).await.unwrap();
+        assert_eq!(tags, vec!["update-me-new-tag".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn update_recipe_rejects_a_nonexistent_id() {
+        let pool = init_db_for_test().await;
+        let result = update_recipe(
+            &pool,
+            &Uuid::new_v4().to_string(),
+            sample_recipe_input("Ghost Chili"),
+            &[],
+            &[],
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn clear_image_nulls_the_image_path_without_touching_other_fields() {
+        let pool = init_db_for_test().await;
+        let mut input = sample_recipe_input("Clear Image Chili");
+        input.image_path = Some("https://example.com/chili.png".to_string());
+        let created = create_recipe(&pool, input).await.unwrap();
+
+        clear_image(&pool, &created.id).await.unwrap();
+
+        let fetched = get_recipe_by_id(&pool, &created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.image_path, None);
+        assert_eq!(fetched.name, "Clear Image Chili");
+    }
+
+    #[tokio::test]
+    async fn clear_image_rejects_a_nonexistent_id() {
+        let pool = init_db_for_test().await;
+        let result = clear_image(&pool, &Uuid::new_v4().to_string()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_recipe_cascades() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("Cascade Chili"))
+            .await
+            .unwrap();
+
+        let ingredient_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO ingredients (id, name) VALUES (?, ?)")
+            .bind(&ingredient_id)
+            .bind(format!("cascade-test-bean-{ingredient_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO recipe_ingredients (id, recipe_id, ingredient_id, quantity, unit) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&recipe.id)
+        .bind(&ingredient_id)
+        .bind(2.0)
+        .bind("cup")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let tag_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tags (id, name) VALUES (?, ?)")
+            .bind(&tag_id)
+            .bind(format!("cascade-test-tag-{tag_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO recipe_tags (recipe_id, tag_id) VALUES (?, ?)")
+            .bind(&recipe.id)
+            .bind(&tag_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        delete_recipe(&pool, &recipe.id).await.unwrap();
+
+        let remaining_ingredients: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM recipe_ingredients WHERE recipe_id = ?")
+                .bind(&recipe.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let remaining_tags: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM recipe_tags WHERE recipe_id = ?")
+                .bind(&recipe.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        assert_eq!(remaining_ingredients, 0);
+        assert_eq!(remaining_tags, 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete_recipe_missing_is_not_found() {
+        let pool = init_db_for_test().await;
+        let result = delete_recipe(&pool, &Uuid::new_v4().to_string()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn add_recipe_ingredient_uses_inferred_category_over_setting() {
+        let pool = init_db_for_test().await;
+        crate::db::settings::set_setting(&pool, "default_import_category", "category-test-pantry")
+            .await
+            .unwrap();
+        let recipe = create_recipe(&pool, sample_recipe_input("Category Test Curry"))
+            .await
+            .unwrap();
+
+        add_recipe_ingredient(
+            &pool,
+            &recipe.id,
+            &RecipeIngredientExport {
+                name: "category-test-curry-powder".to_string(),
+                quantity: 1.0,
+                unit: "tbsp".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            Some("Spices"),
+        )
+        .await
+        .unwrap();
+
+        let category: Option<String> =
+            sqlx::query_scalar("SELECT category FROM ingredients WHERE name = ?")
+                .bind("category-test-curry-powder")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(category, Some("Spices".to_string()));
+    }
+
+    #[tokio::test]
+    async fn add_recipe_ingredient_falls_back_to_configured_default_category() {
+        let pool = init_db_for_test().await;
+        crate::db::settings::set_setting(&pool, "default_import_category", "category-test-pantry")
+            .await
+            .unwrap();
+        let recipe = create_recipe(&pool, sample_recipe_input("Category Test Soup"))
+            .await
+            .unwrap();
+
+        add_recipe_ingredient(
+            &pool,
+            &recipe.id,
+            &RecipeIngredientExport {
+                name: "category-test-mystery-broth".to_string(),
+                quantity: 1.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let category: Option<String> =
+            sqlx::query_scalar("SELECT category FROM ingredients WHERE name = ?")
+                .bind("category-test-mystery-broth")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(category, Some("category-test-pantry".to_string()));
+    }
+
+    #[tokio::test]
+    async fn find_incomplete_recipes_flags_missing_pieces() {
+        let pool = init_db_for_test().await;
+
+        let mut complete = sample_recipe_input("Quality Test Complete Stew");
+        complete.instructions = vec!["Simmer".into()];
+        complete.image_path = Some("/images/stew.png".to_string());
+        let complete = create_recipe(&pool, complete).await.unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &complete.id,
+            &RecipeIngredientExport {
+                name: "quality-test-carrot".to_string(),
+                quantity: 2.0,
+                unit: "ea".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let mut bare = sample_recipe_input("Quality Test Bare Broth");
+        bare.instructions = vec![];
+        bare.image_path = None;
+        let bare = create_recipe(&pool, bare).await.unwrap();
+
+        let issues = find_incomplete_recipes(&pool).await.unwrap();
+        let bare_issue = issues.iter().find(|i| i.recipe_id == bare.id).unwrap();
+        assert!(bare_issue.missing_instructions);
+        assert!(bare_issue.missing_ingredients);
+        assert!(bare_issue.missing_image);
+
+        assert!(issues.iter().all(|i| i.recipe_id != complete.id));
+    }
+
+    #[tokio::test]
+    async fn imported_and_manual_filters_return_only_the_matching_recipe() {
+        let pool = init_db_for_test().await;
+
+        let mut imported = sample_recipe_input("Source Test Imported Tart");
+        imported.source_url = Some("https://example.com/tart".to_string());
+        let imported = create_recipe(&pool, imported).await.unwrap();
+
+        let manual = create_recipe(&pool, sample_recipe_input("Source Test Manual Tart"))
+            .await
+            .unwrap();
+
+        let imported_recipes = get_imported_recipes(&pool).await.unwrap();
+        assert!(imported_recipes.iter().any(|r| r.id == imported.id));
+        assert!(imported_recipes.iter().all(|r| r.id != manual.id));
+
+        let manual_recipes = get_manual_recipes(&pool).await.unwrap();
+        assert!(manual_recipes.iter().any(|r| r.id == manual.id));
+        assert!(manual_recipes.iter().all(|r| r.id != imported.id));
+    }
+
+    #[tokio::test]
+    async fn sorted_by_rating_excludes_unrated_recipes_and_breaks_ties_by_review_count() {
+        let pool = init_db_for_test().await;
+
+        let mut unrated = sample_recipe_input("Rating Test Unrated Soup");
+        unrated.rating_value = None;
+        create_recipe(&pool, unrated).await.unwrap();
+
+        let mut low = sample_recipe_input("Rating Test Low Star Soup");
+        low.rating_value = Some(3.5);
+        low.rating_count = Some(10);
+        let low = create_recipe(&pool, low).await.unwrap();
+
+        let mut high_few_reviews = sample_recipe_input("Rating Test High Star Few Reviews Soup");
+        high_few_reviews.rating_value = Some(4.8);
+        high_few_reviews.rating_count = Some(5);
+        let high_few_reviews = create_recipe(&pool, high_few_reviews).await.unwrap();
+
+        let mut high_many_reviews = sample_recipe_input("Rating Test High Star Many Reviews Soup");
+        high_many_reviews.rating_value = Some(4.8);
+        high_many_reviews.rating_count = Some(500);
+        let high_many_reviews = create_recipe(&pool, high_many_reviews).await.unwrap();
+
+        let sorted = get_recipes_sorted_by_rating(&pool, 10).await.unwrap();
+
+        assert!(sorted.iter().all(|r| r.rating_value.is_some()));
+        let ids: Vec<&str> = sorted.iter().map(|r| r.id.as_str()).collect();
+        let high_many_index = ids
+            .iter()
+            .position(|&id| id == high_many_reviews.id)
+            .unwrap();
+        let high_few_index = ids
+            .iter()
+            .position(|&id| id == high_few_reviews.id)
+            .unwrap();
+        let low_index = ids.iter().position(|&id| id == low.id).unwrap();
+        assert!(high_many_index < high_few_index);
+        assert!(high_few_index < low_index);
+    }
+
+    #[tokio::test]
+    async fn rename_tag_renames_the_row_in_place() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("Rename Test Soup"))
+            .await
+            .unwrap();
+        add_recipe_tag(&pool, &recipe.id, "rename-test-dinnner")
+            .await
+            .unwrap();
+
+        rename_tag(&pool, "rename-test-dinnner", "rename-test-dinner")
+            .await
+            .unwrap();
+
+        let tags = get_recipe_tags(&pool, &recipe.id).await.unwrap();
+        assert_eq!(tags, vec!["rename-test-dinner".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn rename_tag_merges_into_an_existing_tag_without_duplicate_rows() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("Merge Test Soup"))
+            .await
+            .unwrap();
+        add_recipe_tag(&pool, &recipe.id, "merge-test-dinnner")
+            .await
+            .unwrap();
+        add_recipe_tag(&pool, &recipe.id, "merge-test-dinner")
+            .await
+            .unwrap();
+
+        rename_tag(&pool, "merge-test-dinnner", "merge-test-dinner")
+            .await
+            .unwrap();
+
+        let tags = get_recipe_tags(&pool, &recipe.id).await.unwrap();
+        assert_eq!(tags, vec!["merge-test-dinner".to_string()]);
+
+        let remaining_tag_rows: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM tags WHERE name = ?")
+                .bind("merge-test-dinnner")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining_tag_rows, 0);
+    }
+
+    #[tokio::test]
+    async fn rename_tag_of_a_missing_tag_is_not_found() {
+        let pool = init_db_for_test().await;
+        let result = rename_tag(&pool, "rename-test-nonexistent", "rename-test-new").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn get_tag_histogram_counts_and_sorts_descending_and_excludes_zero_count_tags() {
+        let pool = init_db_for_test().await;
+
+        let soup = create_recipe(&pool, sample_recipe_input("Histogram Soup"))
+            .await
+            .unwrap();
+        let stew = create_recipe(&pool, sample_recipe_input("Histogram Stew"))
+            .await
+            .unwrap();
+        let salad = create_recipe(&pool, sample_recipe_input("Histogram Salad"))
+            .await
+            .unwrap();
+
+        add_recipe_tag(&pool, &soup.id, "histogram-comfort")
+            .await
+            .unwrap();
+        add_recipe_tag(&pool, &stew.id, "histogram-comfort")
+            .await
+            .unwrap();
+        add_recipe_tag(&pool, &salad.id, "histogram-light")
+            .await
+            .unwrap();
+
+        // A tag with no recipes left (orphaned after its only recipe's tags
+        // were replaced) should not show up at all.
+        let orphan_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tags (id, name) VALUES (?, ?)")
+            .bind(&orphan_id)
+            .bind("histogram-orphan")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let histogram = get_tag_histogram(&pool).await.unwrap();
+
+        let relevant: Vec<(String, i64)> = histogram
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("histogram-"))
+            .collect();
+
+        assert_eq!(
+            relevant,
+            vec![
+                ("histogram-comfort".to_string(), 2),
+                ("histogram-light".to_string(), 1),
+            ]
+        );
+    }
+
+    fn recipe_with_source(name: &str, source_url: &str) -> RecipeInput {
+        let mut input = sample_recipe_input(name);
+        input.source_url = Some(source_url.to_string());
+        input
+    }
+
+    #[tokio::test]
+    async fn finds_a_recipe_by_an_exact_source_url_match() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(
+            &pool,
+            recipe_with_source(
+                "Source Url Exact",
+                "https://example.com/source-url-test-exact",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let found = get_recipe_by_source_url(&pool, "https://example.com/source-url-test-exact")
+            .await
+            .unwrap();
+
+        assert_eq!(found.unwrap().id, recipe.id);
+    }
+
+    #[tokio::test]
+    async fn finds_a_recipe_by_a_fragment_and_trailing_slash_variant() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(
+            &pool,
+            recipe_with_source(
+                "Source Url Variant",
+                "https://example.com/source-url-test-variant/",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let found =
+            get_recipe_by_source_url(&pool, "https://example.com/source-url-test-variant#top")
+                .await
+                .unwrap();
+
+        assert_eq!(found.unwrap().id, recipe.id);
+    }
+
+    #[tokio::test]
+    async fn an_unknown_source_url_returns_none() {
+        let pool = init_db_for_test().await;
+
+        let found = get_recipe_by_source_url(&pool, "https://example.com/does-not-exist")
+            .await
+            .unwrap();
+
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn set_user_rating_persists_and_clears() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("User Rating Soup"))
+            .await
+            .unwrap();
+
+        let rated = set_user_rating(&pool, &recipe.id, Some(4)).await.unwrap();
+        assert_eq!(rated.user_rating, Some(4));
+
+        let cleared = set_user_rating(&pool, &recipe.id, None).await.unwrap();
+        assert_eq!(cleared.user_rating, None);
+    }
+
+    #[tokio::test]
+    async fn set_user_rating_rejects_ratings_outside_one_to_five() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("User Rating Out Of Range Soup"))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            set_user_rating(&pool, &recipe.id, Some(0)).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            set_user_rating(&pool, &recipe.id, Some(6)).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_recipe_preserves_a_previously_set_user_rating() {
+        let pool = init_db_for_test().await;
+        let created = create_recipe(&pool, sample_recipe_input("Preserve Rating Soup"))
+            .await
+            .unwrap();
+        set_user_rating(&pool, &created.id, Some(5)).await.unwrap();
+
+        let updated = update_recipe(
+            &pool,
+            &created.id,
+            sample_recipe_input("Preserve Rating Soup Updated"),
+            &[],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated.user_rating, Some(5));
+    }
+
+    #[tokio::test]
+    async fn top_rated_recipes_excludes_unrated_and_orders_by_rating_then_name() {
+        let pool = init_db_for_test().await;
+
+        let unrated = create_recipe(&pool, sample_recipe_input("Top Rated Unrated Soup"))
+            .await
+            .unwrap();
+
+        let three_star = create_recipe(&pool, sample_recipe_input("Top Rated Three Star Soup"))
+            .await
+            .unwrap();
+        set_user_rating(&pool, &three_star.id, Some(3))
+            .await
+            .unwrap();
+
+        let five_star_a = create_recipe(&pool, sample_recipe_input("Top Rated Five Star A Soup"))
+            .await
+            .unwrap();
+        set_user_rating(&pool, &five_star_a.id, Some(5))
+            .await
+            .unwrap();
+
+        let five_star_b = create_recipe(&pool, sample_recipe_input("Top Rated Five Star B Soup"))
+            .await
+            .unwrap();
+        set_user_rating(&pool, &five_star_b.id, Some(5))
+            .await
+            .unwrap();
+
+        let top = get_top_rated_recipes(&pool, 10).await.unwrap();
+
+        assert!(top.iter().all(|r| r.id != unrated.id));
+        let ids: Vec<&str> = top.iter().map(|r| r.id.as_str()).collect();
+        let a_index = ids.iter().position(|&id| id == five_star_a.id).unwrap();
+        let b_index = ids.iter().position(|&id| id == five_star_b.id).unwrap();
+        let three_index = ids.iter().position(|&id| id == three_star.id).unwrap();
+        assert!(a_index < three_index);
+        assert!(b_index < three_index);
+        assert!(a_index < b_index);
+    }
+}
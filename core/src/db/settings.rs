@@ -0,0 +1,191 @@
+use sqlx::SqlitePool;
+
+use crate::error::AppError;
+use crate::utils::units::CountRoundingPolicy;
+
+const DEFAULT_IMPORT_CATEGORY_KEY: &str = "default_import_category";
+const FALLBACK_IMPORT_CATEGORY: &str = "Other";
+
+const HTTPS_ONLY_KEY: &str = "https_only";
+
+const SHOPPING_ITEM_RETENTION_DAYS_KEY: &str = "shopping_item_retention_days";
+/// How long a soft-deleted shopping item sticks around before
+/// [`crate::db::purge::purge_old_soft_deleted_shopping_items`] removes it,
+/// when no `shopping_item_retention_days` setting is configured.
+pub const DEFAULT_SHOPPING_ITEM_RETENTION_DAYS: i64 = 30;
+
+pub async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, AppError> {
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+    Ok(value)
+}
+
+pub async fn set_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?, ?) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Resolves the category to store for a newly-imported ingredient.
+///
+/// An `inferred` category (from parsing the recipe itself) always wins, so a
+/// configured default never overrides something the importer already
+/// figured out. Otherwise falls back to the `default_import_category`
+/// setting, and finally to `"Other"` when no setting is configured.
+pub async fn resolve_import_category(
+    pool: &SqlitePool,
+    inferred: Option<&str>,
+) -> Result<String, AppError> {
+    if let Some(category) = inferred {
+        if !category.trim().is_empty() {
+            return Ok(category.to_string());
+        }
+    }
+
+    let configured = get_setting(pool, DEFAULT_IMPORT_CATEGORY_KEY).await?;
+    Ok(configured.unwrap_or_else(|| FALLBACK_IMPORT_CATEGORY.to_string()))
+}
+
+/// Whether imports are restricted to `https` URLs — see
+/// [`crate::importer::validate_url`]. Defaults to `false`, the historical
+/// behavior of allowing both `http` and `https`, so an unconfigured install
+/// doesn't suddenly start rejecting plain-`http` recipe sites.
+pub async fn get_https_only(pool: &SqlitePool) -> Result<bool, AppError> {
+    let configured = get_setting(pool, HTTPS_ONLY_KEY).await?;
+    Ok(configured.as_deref() == Some("true"))
+}
+
+const COUNT_ROUNDING_POLICY_KEY: &str = "count_rounding_policy";
+
+/// The configured `count_rounding_policy` setting — see
+/// [`CountRoundingPolicy`] and [`crate::utils::units::round_count_quantity`].
+/// Defaults to [`CountRoundingPolicy::Up`], since understating how many
+/// eggs to buy is worse than overstating it; an unrecognized value falls
+/// back to the same default rather than erroring.
+pub async fn get_count_rounding_policy(pool: &SqlitePool) -> Result<CountRoundingPolicy, AppError> {
+    let configured = get_setting(pool, COUNT_ROUNDING_POLICY_KEY).await?;
+    Ok(match configured.as_deref() {
+        Some("nearest") => CountRoundingPolicy::Nearest,
+        Some("none") => CountRoundingPolicy::None,
+        _ => CountRoundingPolicy::Up,
+    })
+}
+
+/// The configured `shopping_item_retention_days` setting, falling back to
+/// [`DEFAULT_SHOPPING_ITEM_RETENTION_DAYS`] when unset or unparseable.
+pub async fn get_shopping_item_retention_days(pool: &SqlitePool) -> Result<i64, AppError> {
+    let configured = get_setting(pool, SHOPPING_ITEM_RETENTION_DAYS_KEY).await?;
+    Ok(configured
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_SHOPPING_ITEM_RETENTION_DAYS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+
+    #[tokio::test]
+    async fn resolve_import_category_uses_configured_setting_when_uninferred() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, DEFAULT_IMPORT_CATEGORY_KEY, "settings-test-pantry")
+            .await
+            .unwrap();
+
+        let category = resolve_import_category(&pool, None).await.unwrap();
+
+        assert_eq!(category, "settings-test-pantry");
+    }
+
+    #[tokio::test]
+    async fn resolve_import_category_inferred_wins_over_setting() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, DEFAULT_IMPORT_CATEGORY_KEY, "settings-test-pantry")
+            .await
+            .unwrap();
+
+        let category = resolve_import_category(&pool, Some("Produce"))
+            .await
+            .unwrap();
+
+        assert_eq!(category, "Produce");
+    }
+
+    #[tokio::test]
+    async fn get_setting_returns_none_for_unset_key() {
+        let pool = init_db_for_test().await;
+        let value = get_setting(&pool, "settings-test-never-set-key")
+            .await
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[tokio::test]
+    async fn get_https_only_defaults_to_false() {
+        let pool = init_db_for_test().await;
+
+        let https_only = get_https_only(&pool).await.unwrap();
+
+        assert!(!https_only);
+    }
+
+    #[tokio::test]
+    async fn get_https_only_uses_the_configured_value() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, HTTPS_ONLY_KEY, "true").await.unwrap();
+
+        let https_only = get_https_only(&pool).await.unwrap();
+
+        assert!(https_only);
+    }
+
+    #[tokio::test]
+    async fn get_count_rounding_policy_defaults_to_up() {
+        let pool = init_db_for_test().await;
+
+        let policy = get_count_rounding_policy(&pool).await.unwrap();
+
+        assert_eq!(policy, CountRoundingPolicy::Up);
+    }
+
+    #[tokio::test]
+    async fn get_count_rounding_policy_uses_the_configured_value() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, COUNT_ROUNDING_POLICY_KEY, "nearest")
+            .await
+            .unwrap();
+
+        let policy = get_count_rounding_policy(&pool).await.unwrap();
+
+        assert_eq!(policy, CountRoundingPolicy::Nearest);
+    }
+
+    #[tokio::test]
+    async fn get_shopping_item_retention_days_falls_back_to_the_default() {
+        let pool = init_db_for_test().await;
+
+        let days = get_shopping_item_retention_days(&pool).await.unwrap();
+
+        assert_eq!(days, DEFAULT_SHOPPING_ITEM_RETENTION_DAYS);
+    }
+
+    #[tokio::test]
+    async fn get_shopping_item_retention_days_uses_the_configured_value() {
+        let pool = init_db_for_test().await;
+        set_setting(&pool, SHOPPING_ITEM_RETENTION_DAYS_KEY, "7")
+            .await
+            .unwrap();
+
+        let days = get_shopping_item_retention_days(&pool).await.unwrap();
+
+        assert_eq!(days, 7);
+    }
+}
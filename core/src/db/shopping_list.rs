@@ -0,0 +1,931 @@
+use futures_util::TryStreamExt;
+use sqlx::SqlitePool;
+
+use crate::correlation;
+use crate::db::manual_items::list_items_for_week;
+use crate::db::pantry::list_pantry_items;
+use crate::error::AppError;
+use crate::models::{AggregatedShoppingItem, SharedIngredient, ShoppingItemContribution};
+use crate::utils::ingredient_name::singularize_ingredient_name;
+use crate::utils::units::{convert_quantity, normalize_quantity, round_count_quantity};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct PlannedIngredientRow {
+    recipe_id: String,
+    recipe_servings: i64,
+    meal_plan_servings: i64,
+    name: String,
+    category: Option<String>,
+    quantity: f64,
+    unit: String,
+}
+
+/// Aggregates every ingredient needed across all non-deleted meal plans in
+/// `[start_date, end_date]` into one shopping list, scaling each recipe's
+/// ingredient quantities by how many servings were actually planned versus
+/// the recipe's own serving size. Ingredients with the same
+/// (case-insensitive) name are combined when [`convert_quantity`] can
+/// reconcile their units; otherwise they stay as separate entries. Each
+/// entry's `contributions` breaks the total back down by recipe, computed
+/// before that unit unification collapses the detail.
+///
+/// Rows are folded into `items` as they stream off the cursor rather than
+/// being buffered into a `Vec` first, so peak memory tracks the number of
+/// distinct ingredients rather than the number of planned-ingredient rows —
+/// the difference matters once a range spans a month or a year instead of a
+/// week.
+pub async fn get_aggregated_shopping_list(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<AggregatedShoppingItem>, AppError> {
+    log::debug!(
+        "[cid:{}] aggregating shopping list for {start_date}..{end_date}",
+        correlation::current()
+    );
+
+    let mut rows = sqlx::query_as::<_, PlannedIngredientRow>(
+        "SELECT mp.recipe_id AS recipe_id, r.servings AS recipe_servings, \
+                mp.servings AS meal_plan_servings, i.name AS name, i.category AS category, \
+                ri.quantity AS quantity, ri.unit AS unit \
+         FROM meal_plans mp \
+         JOIN recipes r ON r.id = mp.recipe_id \
+         JOIN recipe_ingredients ri ON ri.recipe_id = r.id \
+         JOIN ingredients i ON i.id = ri.ingredient_id \
+         WHERE mp.is_deleted = 0 AND mp.date BETWEEN ? AND ? \
+         ORDER BY ri.sort_order",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch(pool);
+
+    let mut items: Vec<AggregatedShoppingItem> = Vec::new();
+
+    while let Some(row) = rows.try_next().await? {
+        let scale = if row.recipe_servings > 0 {
+            row.meal_plan_servings as f64 / row.recipe_servings as f64
+        } else {
+            1.0
+        };
+        let scaled_quantity = row.quantity * scale;
+        let normalized_name = singularize_ingredient_name(&row.name);
+
+        let existing = items.iter_mut().find(|item| {
+            singularize_ingredient_name(&item.name) == normalized_name
+                && convert_quantity(scaled_quantity, &row.unit, &item.unit).is_some()
+        });
+
+        match existing {
+            Some(item) => {
+                let converted = convert_quantity(scaled_quantity, &row.unit, &item.unit)
+                    .expect("just checked convertibility above");
+                item.quantity += converted;
+                item.source_recipe_ids.push(row.recipe_id.clone());
+                item.contributions.push(ShoppingItemContribution {
+                    recipe_id: row.recipe_id,
+                    quantity: scaled_quantity,
+                    unit: row.unit,
+                });
+            }
+            None => {
+                items.push(AggregatedShoppingItem {
+                    name: row.name,
+                    category: row.category,
+                    quantity: scaled_quantity,
+                    unit: row.unit.clone(),
+                    source_recipe_ids: vec![row.recipe_id.clone()],
+                    contributions: vec![ShoppingItemContribution {
+                        recipe_id: row.recipe_id,
+                        quantity: scaled_quantity,
+                        unit: row.unit,
+                    }],
+                });
+            }
+        }
+    }
+
+    let rounding_policy = crate::db::settings::get_count_rounding_policy(pool).await?;
+    for item in &mut items {
+        let (quantity, unit) = normalize_quantity(item.quantity, &item.unit);
+        item.quantity = round_count_quantity(quantity, &unit, rounding_policy);
+        item.unit = unit;
+    }
+
+    Ok(items)
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct SharedIngredientRow {
+    recipe_id: String,
+    recipe_name: String,
+    ingredient_name: String,
+}
+
+/// Ingredients (case/whitespace/plural insensitive, via
+/// [`singularize_ingredient_name`]) used by more than one distinct planned
+/// recipe in `[start_date, end_date]`, for batch-prep planning ("onions
+/// appear in 4 of this week's recipes"). Reuses the same
+/// meal-plan/recipe/ingredient join [`get_aggregated_shopping_list`] does,
+/// but groups by ingredient rather than summing quantities, and a recipe
+/// planned more than once in the range only counts once.
+pub async fn get_shared_ingredients(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<SharedIngredient>, AppError> {
+    let rows = sqlx::query_as::<_, SharedIngredientRow>(
+        "SELECT DISTINCT mp.recipe_id AS recipe_id, r.name AS recipe_name, \
+                i.name AS ingredient_name \
+         FROM meal_plans mp \
+         JOIN recipes r ON r.id = mp.recipe_id \
+         JOIN recipe_ingredients ri ON ri.recipe_id = r.id \
+         JOIN ingredients i ON i.id = ri.ingredient_id \
+         WHERE mp.is_deleted = 0 AND mp.date BETWEEN ? AND ?",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await?;
+
+    let mut grouped: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for row in rows {
+        let normalized_name = singularize_ingredient_name(&row.ingredient_name);
+        match grouped
+            .iter_mut()
+            .find(|(name, _)| singularize_ingredient_name(name) == normalized_name)
+        {
+            Some((_, recipes)) => recipes.push((row.recipe_id, row.recipe_name)),
+            None => grouped.push((row.ingredient_name, vec![(row.recipe_id, row.recipe_name)])),
+        }
+    }
+
+    let shared = grouped
+        .into_iter()
+        .filter(|(_, recipes)| recipes.len() > 1)
+        .map(|(name, recipes)| SharedIngredient {
+            name,
+            recipe_count: recipes.len() as i64,
+            recipe_names: recipes.into_iter().map(|(_, name)| name).collect(),
+        })
+        .collect();
+
+    Ok(shared)
+}
+
+/// Like [`get_aggregated_shopping_list`], but nets out what's already
+/// covered: pantry stock on hand (when `subtract_pantry` is true) and
+/// whatever's already on `week_start`'s manual shopping list, both matched
+/// by (singularized) ingredient name and reconciled through
+/// [`convert_quantity`] the same way aggregation itself combines units. An
+/// ingredient fully covered by pantry plus the list drops out entirely
+/// rather than appearing with a zero (or negative) quantity; a partially
+/// covered one keeps its reduced remainder, renormalized the same way
+/// [`get_aggregated_shopping_list`] normalizes its totals.
+pub async fn get_week_shopping_gap(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    week_start: &str,
+    subtract_pantry: bool,
+) -> Result<Vec<AggregatedShoppingItem>, AppError> {
+    let mut items = get_aggregated_shopping_list(pool, start_date, end_date).await?;
+    let rounding_policy = crate::db::settings::get_count_rounding_policy(pool).await?;
+
+    let pantry_items = if subtract_pantry {
+        list_pantry_items(pool).await?
+    } else {
+        Vec::new()
+    };
+
+    let listed_items = list_items_for_week(pool, week_start).await?;
+
+    items.retain_mut(|item| {
+        let normalized_name = singularize_ingredient_name(&item.name);
+
+        let already_have: f64 = pantry_items
+            .iter()
+            .filter(|pantry| singularize_ingredient_name(&pantry.name) == normalized_name)
+            .filter_map(|pantry| {
+                convert_quantity(
+                    pantry.quantity.unwrap_or(0.0),
+                    pantry.unit.as_deref().unwrap_or(""),
+                    &item.unit,
+                )
+            })
+            .chain(
+                listed_items
+                    .iter()
+                    .filter(|listed| singularize_ingredient_name(&listed.name) == normalized_name)
+                    .filter_map(|listed| {
+                        convert_quantity(
+                            listed.quantity.unwrap_or(0.0),
+                            listed.unit.as_deref().unwrap_or(""),
+                            &item.unit,
+                        )
+                    }),
+            )
+            .sum();
+
+        item.quantity -= already_have;
+        if item.quantity <= 1e-9 {
+            return false;
+        }
+
+        let (quantity, unit) = normalize_quantity(item.quantity, &item.unit);
+        item.quantity = round_count_quantity(quantity, &unit, rounding_policy);
+        item.unit = unit;
+        true
+    });
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::manual_items::create_manual_item;
+    use crate::db::meal_plans::create_meal_plan;
+    use crate::db::pantry::add_pantry_item;
+    use crate::db::pool::init_db_for_test;
+    use crate::db::recipes::{add_recipe_ingredient, create_recipe};
+    use crate::models::{
+        ManualShoppingItemInput, MealPlanInput, PantryItemInput, RecipeIngredientExport,
+        RecipeInput,
+    };
+
+    fn recipe_input(name: &str, servings: i64) -> RecipeInput {
+        RecipeInput {
+            name: name.to_string(),
+            description: None,
+            servings,
+            yield_unit: None,
+            prep_time: None,
+            cook_time: None,
+            instructions: vec!["Cook".into()],
+            image_path: None,
+            source_url: None,
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn contributions_sum_to_the_aggregated_total() {
+        let pool = init_db_for_test().await;
+
+        let frittata = create_recipe(&pool, recipe_input("Shopping Agg Frittata", 2))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &frittata.id,
+            &RecipeIngredientExport {
+                name: "shopping-agg eggs".to_string(),
+                quantity: 4.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let pancakes = create_recipe(&pool, recipe_input("Shopping Agg Pancakes", 4))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &pancakes.id,
+            &RecipeIngredientExport {
+                name: "shopping-agg eggs".to_string(),
+                quantity: 2.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: frittata.id.clone(),
+                date: "2026-08-03".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: pancakes.id.clone(),
+                date: "2026-08-04".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(8),
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = get_aggregated_shopping_list(&pool, "2026-08-03", "2026-08-04")
+            .await
+            .unwrap();
+
+        let eggs = items
+            .iter()
+            .find(|item| item.name == "shopping-agg eggs")
+            .expect("eggs should be aggregated");
+
+        // frittata: 4 eggs at 2/2 servings, pancakes: 2 eggs scaled to 8/4 servings = 4
+        assert_eq!(eggs.quantity, 8.0);
+        assert_eq!(eggs.source_recipe_ids.len(), 2);
+
+        let contribution_total: f64 = eggs.contributions.iter().map(|c| c.quantity).sum();
+        assert_eq!(contribution_total, eggs.quantity);
+
+        let frittata_contribution = eggs
+            .contributions
+            .iter()
+            .find(|c| c.recipe_id == frittata.id)
+            .unwrap();
+        assert_eq!(frittata_contribution.quantity, 4.0);
+
+        let pancakes_contribution = eggs
+            .contributions
+            .iter()
+            .find(|c| c.recipe_id == pancakes.id)
+            .unwrap();
+        assert_eq!(pancakes_contribution.quantity, 4.0);
+    }
+
+    #[tokio::test]
+    async fn streaming_aggregation_matches_manual_totals_across_many_meal_plans() {
+        let pool = init_db_for_test().await;
+
+        let flour = create_recipe(&pool, recipe_input("Stream Agg Bread", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &flour.id,
+            &RecipeIngredientExport {
+                name: "stream-agg flour".to_string(),
+                quantity: 1.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Plan the same recipe on every day of a month so the row count far
+        // exceeds the number of distinct ingredients the fold collapses
+        // them into.
+        for day in 1..=30 {
+            create_meal_plan(
+                &pool,
+                MealPlanInput {
+                    recipe_id: flour.id.clone(),
+                    date: format!("2026-07-{day:02}"),
+                    meal_type: "dinner".to_string(),
+                    servings: Some(1),
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let items = get_aggregated_shopping_list(&pool, "2026-07-01", "2026-07-30")
+            .await
+            .unwrap();
+
+        let flour_item = items
+            .iter()
+            .find(|item| item.name == "stream-agg flour")
+            .expect("flour should be aggregated");
+
+        assert_eq!(flour_item.quantity, 30.0);
+        assert_eq!(flour_item.source_recipe_ids.len(), 30);
+        assert_eq!(flour_item.contributions.len(), 30);
+    }
+
+    #[tokio::test]
+    async fn count_items_round_up_to_a_whole_number_by_default() {
+        let pool = init_db_for_test().await;
+
+        let omelette = create_recipe(&pool, recipe_input("Rounding Omelette", 2))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &omelette.id,
+            &RecipeIngredientExport {
+                name: "rounding-policy eggs".to_string(),
+                quantity: 5.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: omelette.id.clone(),
+                date: "2026-08-10".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = get_aggregated_shopping_list(&pool, "2026-08-10", "2026-08-10")
+            .await
+            .unwrap();
+
+        let eggs = items
+            .iter()
+            .find(|item| item.name == "rounding-policy eggs")
+            .unwrap();
+        // 5 eggs at 1/2 servings = 2.5, rounded up to 3.
+        assert_eq!(eggs.quantity, 3.0);
+    }
+
+    #[tokio::test]
+    async fn volume_items_stay_fractional_regardless_of_rounding_policy() {
+        let pool = init_db_for_test().await;
+
+        let pancakes = create_recipe(&pool, recipe_input("Rounding Pancakes", 4))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &pancakes.id,
+            &RecipeIngredientExport {
+                name: "rounding-policy flour".to_string(),
+                quantity: 2.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: pancakes.id.clone(),
+                date: "2026-08-11".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(5),
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = get_aggregated_shopping_list(&pool, "2026-08-11", "2026-08-11")
+            .await
+            .unwrap();
+
+        let flour = items
+            .iter()
+            .find(|item| item.name == "rounding-policy flour")
+            .unwrap();
+        // 2 cups at 5/4 servings = 2.5 cups, untouched by the count policy.
+        assert_eq!(flour.quantity, 2.5);
+    }
+
+    #[tokio::test]
+    async fn count_items_stay_fractional_under_the_none_policy() {
+        let pool = init_db_for_test().await;
+        crate::db::settings::set_setting(&pool, "count_rounding_policy", "none")
+            .await
+            .unwrap();
+
+        let omelette = create_recipe(&pool, recipe_input("Rounding None Omelette", 2))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &omelette.id,
+            &RecipeIngredientExport {
+                name: "rounding-policy-none eggs".to_string(),
+                quantity: 5.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: omelette.id.clone(),
+                date: "2026-08-12".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = get_aggregated_shopping_list(&pool, "2026-08-12", "2026-08-12")
+            .await
+            .unwrap();
+
+        let eggs = items
+            .iter()
+            .find(|item| item.name == "rounding-policy-none eggs")
+            .unwrap();
+        assert_eq!(eggs.quantity, 2.5);
+    }
+
+    #[tokio::test]
+    async fn get_shared_ingredients_reports_an_ingredient_used_by_two_recipes() {
+        let pool = init_db_for_test().await;
+
+        let frittata = create_recipe(&pool, recipe_input("Shared Ing Frittata", 2))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &frittata.id,
+            &RecipeIngredientExport {
+                name: "shared-ing onion".to_string(),
+                quantity: 1.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let soup = create_recipe(&pool, recipe_input("Shared Ing Soup", 4))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &soup.id,
+            &RecipeIngredientExport {
+                name: "shared-ing onion".to_string(),
+                quantity: 2.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &soup.id,
+            &RecipeIngredientExport {
+                name: "shared-ing broth".to_string(),
+                quantity: 1.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 1,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: frittata.id.clone(),
+                date: "2026-08-03".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: soup.id.clone(),
+                date: "2026-08-04".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+
+        let shared = get_shared_ingredients(&pool, "2026-08-03", "2026-08-04")
+            .await
+            .unwrap();
+
+        let onion = shared
+            .iter()
+            .find(|item| item.name == "shared-ing onion")
+            .expect("onion should be reported as shared");
+        assert_eq!(onion.recipe_count, 2);
+        assert!(onion
+            .recipe_names
+            .contains(&"Shared Ing Frittata".to_string()));
+        assert!(onion.recipe_names.contains(&"Shared Ing Soup".to_string()));
+
+        assert!(!shared.iter().any(|item| item.name == "shared-ing broth"));
+    }
+
+    #[tokio::test]
+    async fn merges_singular_and_plural_spellings_of_the_same_ingredient() {
+        let pool = init_db_for_test().await;
+
+        let omelette = create_recipe(&pool, recipe_input("Shopping Agg Omelette", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &omelette.id,
+            &RecipeIngredientExport {
+                name: "shopping-agg-plural-egg".to_string(),
+                quantity: 2.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let scramble = create_recipe(&pool, recipe_input("Shopping Agg Scramble", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &scramble.id,
+            &RecipeIngredientExport {
+                name: "shopping-agg-plural-eggs".to_string(),
+                quantity: 3.0,
+                unit: String::new(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: omelette.id.clone(),
+                date: "2026-08-05".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: scramble.id.clone(),
+                date: "2026-08-06".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = get_aggregated_shopping_list(&pool, "2026-08-05", "2026-08-06")
+            .await
+            .unwrap();
+
+        let matching: Vec<_> = items
+            .iter()
+            .filter(|item| item.name.to_lowercase().contains("shopping-agg-plural-egg"))
+            .collect();
+        assert_eq!(
+            matching.len(),
+            1,
+            "singular and plural spellings should merge into one item"
+        );
+        assert_eq!(matching[0].quantity, 5.0);
+    }
+
+    #[tokio::test]
+    async fn an_ingredient_fully_covered_by_pantry_and_list_drops_out() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-14";
+
+        let bread = create_recipe(&pool, recipe_input("Shopping Gap Bread", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &bread.id,
+            &RecipeIngredientExport {
+                name: "shopping-gap flour".to_string(),
+                quantity: 4.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: bread.id.clone(),
+                date: "2026-09-14".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "shopping-gap flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cup".to_string()),
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "shopping-gap flour".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let gap = get_week_shopping_gap(&pool, "2026-09-14", "2026-09-14", week, true)
+            .await
+            .unwrap();
+
+        assert!(
+            gap.iter().all(|item| item.name != "shopping-gap flour"),
+            "fully covered ingredient should drop out"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_partially_covered_ingredient_is_reduced() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-21";
+
+        let soup = create_recipe(&pool, recipe_input("Shopping Gap Soup", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &soup.id,
+            &RecipeIngredientExport {
+                name: "shopping-gap-partial carrots".to_string(),
+                quantity: 5.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: soup.id.clone(),
+                date: "2026-09-21".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "shopping-gap-partial carrots".to_string(),
+                quantity: Some(2.0),
+                unit: Some("cup".to_string()),
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: week.to_string(),
+                name: "shopping-gap-partial carrots".to_string(),
+                quantity: Some(1.0),
+                unit: Some("cup".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let gap = get_week_shopping_gap(&pool, "2026-09-21", "2026-09-21", week, true)
+            .await
+            .unwrap();
+
+        let carrots = gap
+            .iter()
+            .find(|item| item.name == "shopping-gap-partial carrots")
+            .expect("partially covered ingredient should remain");
+        assert_eq!(carrots.quantity, 2.0);
+    }
+
+    #[tokio::test]
+    async fn subtract_pantry_false_ignores_pantry_stock() {
+        let pool = init_db_for_test().await;
+        let week = "2026-09-28";
+
+        let stew = create_recipe(&pool, recipe_input("Shopping Gap Stew", 1))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &stew.id,
+            &RecipeIngredientExport {
+                name: "shopping-gap-nopantry onions".to_string(),
+                quantity: 3.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: stew.id.clone(),
+                date: "2026-09-28".to_string(),
+                meal_type: "dinner".to_string(),
+                servings: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        add_pantry_item(
+            &pool,
+            PantryItemInput {
+                name: "shopping-gap-nopantry onions".to_string(),
+                quantity: Some(3.0),
+                unit: Some("cup".to_string()),
+                category: None,
+                restock_threshold: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let gap = get_week_shopping_gap(&pool, "2026-09-28", "2026-09-28", week, false)
+            .await
+            .unwrap();
+
+        let onions = gap
+            .iter()
+            .find(|item| item.name == "shopping-gap-nopantry onions")
+            .expect("pantry stock should be ignored when subtract_pantry is false");
+        assert_eq!(onions.quantity, 3.0);
+    }
+}
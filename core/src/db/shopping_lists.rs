@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::ShoppingList;
+
+const DEFAULT_LIST_NAME: &str = "This Week";
+
+/// How many times [`get_or_create_week_list`] re-checks for a concurrent
+/// caller's row after losing the insert race, before giving up — SQLite's
+/// default (non-WAL) journal mode can report `SQLITE_BUSY` while the
+/// winner's write is still in flight, not only once it's committed, so the
+/// row isn't always visible on the very first re-`SELECT`.
+const CREATE_RACE_POLL_ATTEMPTS: u32 = 20;
+const CREATE_RACE_POLL_DELAY: Duration = Duration::from_millis(5);
+
+/// Returns `week_start`'s default shopping list, creating it (named
+/// [`DEFAULT_LIST_NAME`]) the first time it's asked for. A custom list a
+/// user has already named for this week doesn't satisfy the lookup, since
+/// it isn't `list_type = 'default'`.
+///
+/// Two concurrent callers for a week with no default yet can both pass the
+/// initial lookup and both attempt the `INSERT` — only one actually lands
+/// it, whether that surfaces as a collision with the partial unique index
+/// on `(week_start) WHERE list_type = 'default'` or as a raw
+/// `SQLITE_BUSY` from the other writer. Rather than propagate either as an
+/// error, the loser re-`SELECT`s for the row the winner is creating (see
+/// [`CREATE_RACE_POLL_ATTEMPTS`]) and returns that instead of a duplicate.
+pub async fn get_or_create_week_list(
+    pool: &SqlitePool,
+    week_start: &str,
+) -> Result<ShoppingList, AppError> {
+    if let Some(existing) = fetch_default_list(pool, week_start).await? {
+        return Ok(existing);
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let inserted = sqlx::query(
+        "INSERT INTO shopping_lists (id, week_start, name, list_type) \
+         VALUES (?, ?, ?, 'default')",
+    )
+    .bind(&id)
+    .bind(week_start)
+    .bind(DEFAULT_LIST_NAME)
+    .execute(pool)
+    .await;
+
+    match inserted {
+        Ok(_) => sqlx::query_as::<_, ShoppingList>("SELECT * FROM shopping_lists WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await
+            .map_err(AppError::from),
+        Err(err) if is_create_race_error(&err) => wait_for_concurrent_default(pool, week_start).await,
+        Err(err) => Err(AppError::Database(err)),
+    }
+}
+
+async fn fetch_default_list(
+    pool: &SqlitePool,
+    week_start: &str,
+) -> Result<Option<ShoppingList>, AppError> {
+    sqlx::query_as::<_, ShoppingList>(
+        "SELECT * FROM shopping_lists WHERE week_start = ? AND list_type = 'default'",
+    )
+    .bind(week_start)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::from)
+}
+
+/// True for the two ways a racing `INSERT` into `shopping_lists` can fail
+/// when a concurrent caller already won (or is winning) the same week's
+/// default list: a unique-constraint violation once the winner has
+/// committed, or `SQLITE_BUSY` (extended result code `5`) while it's still
+/// mid-write.
+fn is_create_race_error(err: &sqlx::Error) -> bool {
+    err.as_database_error().is_some_and(|db_err| {
+        db_err.is_unique_violation() || db_err.code().as_deref() == Some("5")
+    })
+}
+
+async fn wait_for_concurrent_default(
+    pool: &SqlitePool,
+    week_start: &str,
+) -> Result<ShoppingList, AppError> {
+    for attempt in 0..CREATE_RACE_POLL_ATTEMPTS {
+        if let Some(existing) = fetch_default_list(pool, week_start).await? {
+            return Ok(existing);
+        }
+        if attempt + 1 < CREATE_RACE_POLL_ATTEMPTS {
+            tokio::time::sleep(CREATE_RACE_POLL_DELAY).await;
+        }
+    }
+    Err(AppError::Conflict(format!(
+        "default shopping list for '{week_start}' is still being created by another request"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+
+    #[tokio::test]
+    async fn the_first_call_for_a_week_creates_the_default_list() {
+        let pool = init_db_for_test().await;
+
+        let list = get_or_create_week_list(&pool, "2026-08-10").await.unwrap();
+
+        assert_eq!(list.week_start, "2026-08-10");
+        assert_eq!(list.name, "This Week");
+        assert_eq!(list.list_type, "default");
+    }
+
+    #[tokio::test]
+    async fn the_second_call_for_a_week_returns_the_same_list() {
+        let pool = init_db_for_test().await;
+
+        let first = get_or_create_week_list(&pool, "2026-08-10").await.unwrap();
+        let second = get_or_create_week_list(&pool, "2026-08-10").await.unwrap();
+
+        assert_eq!(first.id, second.id);
+    }
+
+    #[tokio::test]
+    async fn a_custom_list_in_the_same_week_is_not_treated_as_the_default() {
+        let pool = init_db_for_test().await;
+        sqlx::query(
+            "INSERT INTO shopping_lists (id, week_start, name, list_type) \
+             VALUES ('custom-1', '2026-08-10', 'Party Supplies', 'custom')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let list = get_or_create_week_list(&pool, "2026-08-10").await.unwrap();
+
+        assert_ne!(list.id, "custom-1");
+        assert_eq!(list.list_type, "default");
+    }
+
+    #[tokio::test]
+    async fn concurrent_calls_for_the_same_unstarted_week_agree_on_one_default_list() {
+        let pool = init_db_for_test().await;
+
+        let (first, second) = tokio::join!(
+            get_or_create_week_list(&pool, "2026-08-10"),
+            get_or_create_week_list(&pool, "2026-08-10"),
+        );
+
+        assert_eq!(first.unwrap().id, second.unwrap().id);
+    }
+}
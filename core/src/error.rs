@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+/// Application-wide error type returned by every `db` and `commands` function.
+///
+/// Command handlers convert this into a plain `String` (via `From<AppError>
+/// for String`) since Tauri commands serialize their error type directly to
+/// the frontend.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+
+    #[error(transparent)]
+    Migration(#[from] sqlx::migrate::MigrateError),
+
+    #[error(transparent)]
+    Fetch(#[from] reqwest::Error),
+
+    #[error("{0}")]
+    InvalidContentType(String),
+
+    #[error("{0}")]
+    ResponseTooLarge(String),
+
+    #[error("{0}")]
+    EmptyResponse(String),
+
+    #[error(transparent)]
+    Parse(#[from] crate::parser::error::ParseError),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+/// Serializable shape sent to the frontend when a command fails, giving it a
+/// stable `code` to branch on instead of parsing the message text.
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+}
+
+impl From<&AppError> for ErrorResponse {
+    fn from(err: &AppError) -> Self {
+        let code = match err {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Validation(_) => "VALIDATION",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::Database(_) => "DATABASE",
+            AppError::Migration(_) => "DATABASE",
+            AppError::Fetch(_) => "FETCH",
+            AppError::InvalidContentType(_) => "INVALID_CONTENT_TYPE",
+            AppError::ResponseTooLarge(_) => "RESPONSE_TOO_LARGE",
+            AppError::EmptyResponse(_) => "EMPTY_RESPONSE",
+            AppError::Parse(_) => "PARSE",
+            AppError::Internal(_) => "INTERNAL",
+        };
+        ErrorResponse {
+            code: code.to_string(),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        let response = ErrorResponse::from(&err);
+        let message = response.message.clone();
+        serde_json::to_string(&response).unwrap_or(message)
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_conflict_maps_to_the_conflict_code_and_keeps_its_message() {
+        let err = AppError::Conflict("a recipe from this URL already exists".to_string());
+
+        let response = ErrorResponse::from(&err);
+
+        assert_eq!(response.code, "CONFLICT");
+        assert_eq!(response.message, "a recipe from this URL already exists");
+    }
+
+    #[test]
+    fn converting_a_conflict_into_a_string_serializes_its_code_and_message() {
+        let err = AppError::Conflict("duplicate week".to_string());
+
+        let serialized: String = err.into();
+
+        assert!(serialized.contains("\"code\":\"CONFLICT\""));
+        assert!(serialized.contains("duplicate week"));
+    }
+}
@@ -0,0 +1,159 @@
+use csv::Writer;
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::AggregatedShoppingItem;
+use crate::utils::units::format_quantity_for_display;
+
+/// Renders `items` as RFC 4180 CSV with a `name,quantity,unit,category`
+/// header, for users who track grocery spend in a spreadsheet. Delegates
+/// quoting and escaping to the `csv` crate rather than joining fields by
+/// hand, so a name or category containing a comma or quote can't corrupt
+/// the row. Quantity and unit go through [`format_quantity_for_display`],
+/// the same helper [`aggregated_shopping_list_to_text`] uses, so a weight
+/// reads as "1.5 kg" here too instead of raw `f64` formatting showing
+/// "1500" next to a separate "g" column.
+pub fn aggregated_shopping_list_to_csv(
+    items: &[AggregatedShoppingItem],
+) -> Result<String, AppError> {
+    let mut writer = Writer::from_writer(vec![]);
+    writer
+        .write_record(["name", "quantity", "unit", "category"])
+        .map_err(|e| AppError::Internal(format!("failed to write CSV header: {e}")))?;
+
+    for item in items {
+        let (quantity, unit) = format_quantity_for_display(item.quantity, &item.unit);
+        writer
+            .write_record([
+                item.name.as_str(),
+                &quantity,
+                unit.as_str(),
+                item.category.as_deref().unwrap_or(""),
+            ])
+            .map_err(|e| AppError::Internal(format!("failed to write CSV row: {e}")))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(format!("failed to flush CSV writer: {e}")))?;
+    String::from_utf8(bytes)
+        .map_err(|e| AppError::Internal(format!("CSV output was not valid UTF-8: {e}")))
+}
+
+/// Renders `items` as plain text, one `- {quantity} {unit} {name}` line
+/// per item, going through the same [`format_quantity_for_display`] helper
+/// as [`aggregated_shopping_list_to_csv`] so the two exports never drift
+/// apart on how a given item's quantity reads.
+pub fn aggregated_shopping_list_to_text(items: &[AggregatedShoppingItem]) -> String {
+    let mut text = String::new();
+    for item in items {
+        let (quantity, unit) = format_quantity_for_display(item.quantity, &item.unit);
+        text.push_str(&format!("- {quantity} {unit} {}\n", item.name));
+    }
+    text
+}
+
+/// Fetches the aggregated shopping list for `start_date`..`end_date` and
+/// renders it as CSV — see [`aggregated_shopping_list_to_csv`].
+pub async fn export_aggregated_shopping_list_csv(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<String, AppError> {
+    let items = db::shopping_list::get_aggregated_shopping_list(pool, start_date, end_date).await?;
+    aggregated_shopping_list_to_csv(&items)
+}
+
+/// Like [`export_aggregated_shopping_list_csv`], but renders the result as
+/// plain text via [`aggregated_shopping_list_to_text`].
+pub async fn export_aggregated_shopping_list_text(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<String, AppError> {
+    let items = db::shopping_list::get_aggregated_shopping_list(pool, start_date, end_date).await?;
+    Ok(aggregated_shopping_list_to_text(&items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        name: &str,
+        quantity: f64,
+        unit: &str,
+        category: Option<&str>,
+    ) -> AggregatedShoppingItem {
+        AggregatedShoppingItem {
+            name: name.to_string(),
+            category: category.map(String::from),
+            quantity,
+            unit: unit.to_string(),
+            source_recipe_ids: Vec::new(),
+            contributions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn includes_the_header_row() {
+        let csv = aggregated_shopping_list_to_csv(&[]).unwrap();
+
+        assert_eq!(csv, "name,quantity,unit,category\n");
+    }
+
+    #[test]
+    fn renders_a_normal_row() {
+        let csv =
+            aggregated_shopping_list_to_csv(&[item("Flour", 2.5, "cup", Some("Pantry"))]).unwrap();
+
+        assert_eq!(csv, "name,quantity,unit,category\nFlour,2 1/2,cup,Pantry\n");
+    }
+
+    #[test]
+    fn promotes_a_large_weight_to_kilograms() {
+        let csv =
+            aggregated_shopping_list_to_csv(&[item("Flour", 1500.0, "g", Some("Pantry"))]).unwrap();
+
+        assert_eq!(csv, "name,quantity,unit,category\nFlour,1 1/2,kg,Pantry\n");
+    }
+
+    #[test]
+    fn csv_and_text_exports_render_the_same_item_identically() {
+        let items = [item("Flour", 1500.0, "g", Some("Pantry"))];
+
+        let csv = aggregated_shopping_list_to_csv(&items).unwrap();
+        let text = aggregated_shopping_list_to_text(&items);
+
+        assert!(csv.contains("Flour,1 1/2,kg,Pantry"));
+        assert_eq!(text, "- 1 1/2 kg Flour\n");
+    }
+
+    #[test]
+    fn quotes_a_name_containing_a_comma() {
+        let csv =
+            aggregated_shopping_list_to_csv(&[item("Salt, kosher", 1.0, "tsp", None)]).unwrap();
+
+        assert_eq!(
+            csv,
+            "name,quantity,unit,category\n\"Salt, kosher\",1,tsp,\n"
+        );
+    }
+
+    #[test]
+    fn escapes_a_category_containing_a_quote() {
+        let csv = aggregated_shopping_list_to_csv(&[item(
+            "Tomatoes",
+            1.0,
+            "can",
+            Some("Grandma's \"secret\" stash"),
+        )])
+        .unwrap();
+
+        assert_eq!(
+            csv,
+            "name,quantity,unit,category\nTomatoes,1,can,\"Grandma's \"\"secret\"\" stash\"\n"
+        );
+    }
+}
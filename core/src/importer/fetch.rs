@@ -0,0 +1,570 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+
+use crate::error::AppError;
+
+/// How many times [`fetch_url`] will attempt a request before giving up —
+/// the original attempt plus this many retries.
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_millis(250);
+const MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// A response declaring a larger `Content-Length` than this is rejected
+/// before its body is read at all — a recipe page has no business being
+/// anywhere near this large, and reading it in full would otherwise be a
+/// cheap way for a malicious or misbehaving server to burn memory.
+pub(crate) const MAX_RESPONSE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Minimum body length [`fetch_url`] demands from a response that doesn't
+/// itself contain `<html` — below this, and without that tag, the body is
+/// treated as a CDN/WAF soft-block page (an "access denied" notice or an
+/// empty shell) returned with a `200` status rather than a real error.
+/// Left unchecked, such a page would make `fetch_url` succeed only for the
+/// parser to fail downstream with a confusing `NoJsonLd`.
+const MIN_HTML_BODY_LEN: usize = 300;
+
+/// The client [`fetch_url`] and [`fetch_json_url`] fall back to when the
+/// caller doesn't need to inject one of their own — plain default
+/// `reqwest` configuration, kept in one place so a client built for
+/// injection (see [`fetch_url_with_client`]) starts from the same baseline.
+fn default_client() -> reqwest::Client {
+    reqwest::Client::new()
+}
+
+/// Downloads `url` and returns the response body as a `String`, but only
+/// when the content looks like HTML.
+///
+/// Servers are not always honest about `Content-Type`: some serve HTML as
+/// `text/plain` or `application/octet-stream`. When the declared type isn't
+/// recognized as HTML, we fall back to sniffing the body itself before
+/// giving up, so the importer keeps working against those sites without
+/// accepting genuinely non-HTML payloads (JSON, binary, etc).
+///
+/// A timeout, connection failure, or server error (5xx) — the kind of thing
+/// a site having a bad minute produces, as opposed to a page that's simply
+/// not a recipe — is retried up to [`MAX_ATTEMPTS`] times with jittered
+/// exponential backoff (see [`backoff_delay`]), rather than failing the
+/// whole bulk import over what's often a transient blip.
+pub async fn fetch_url(url: &str) -> Result<String, AppError> {
+    fetch_url_with_client(&default_client(), url).await
+}
+
+/// Like [`fetch_url`], but against a caller-supplied client rather than one
+/// built internally. This is the seam tests use to inject a client
+/// preconfigured with a custom user agent, timeout, or other `reqwest`
+/// builder option, instead of having to spin up wiremock just to assert
+/// that configuration actually took effect.
+pub async fn fetch_url_with_client(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<String, AppError> {
+    fetch_with_retry(client, url, "HTML", true, |content_type, body| {
+        content_type.contains("text/html")
+            || content_type.contains("application/xhtml+xml")
+            || sniffs_as_html(body)
+    })
+    .await
+}
+
+/// Like [`fetch_url`], but for the JSON escape hatch some client-rendered
+/// recipe apps expose instead of JSON-LD: accepts a response whose
+/// declared or sniffed content is JSON rather than HTML, for
+/// [`crate::importer::import_recipe_from_url`] to feed straight into
+/// [`crate::parser::recipe::parse_recipe_json`].
+pub async fn fetch_json_url(url: &str) -> Result<String, AppError> {
+    fetch_with_retry(&default_client(), url, "JSON", false, |content_type, body| {
+        content_type.contains("application/json") || content_type.contains("text/json") || {
+            let trimmed = body.trim_start();
+            trimmed.starts_with('{') || trimmed.starts_with('[')
+        }
+    })
+    .await
+}
+
+async fn fetch_with_retry(
+    client: &reqwest::Client,
+    url: &str,
+    expected: &str,
+    check_empty_html: bool,
+    accepts: impl Fn(&str, &str) -> bool,
+) -> Result<String, AppError> {
+    let mut attempt = 0;
+    loop {
+        match fetch_once(client, url, expected, check_empty_html, &accepts).await {
+            Ok(body) => return Ok(body),
+            Err(err) if attempt + 1 < MAX_ATTEMPTS && is_retryable(&err) => {
+                let delay = backoff_delay(attempt, BASE_DELAY, MAX_DELAY, retry_jitter_seed());
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+async fn fetch_once(
+    client: &reqwest::Client,
+    url: &str,
+    expected: &str,
+    check_empty_html: bool,
+    accepts: impl Fn(&str, &str) -> bool,
+) -> Result<String, AppError> {
+    let response = client.get(url).send().await?.error_for_status()?;
+
+    if let Some(len) = response.content_length() {
+        if len > MAX_RESPONSE_SIZE {
+            return Err(AppError::ResponseTooLarge(format!(
+                "response declared {len} bytes, over the {MAX_RESPONSE_SIZE} byte cap"
+            )));
+        }
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    let body = read_body_within_size_limit(response).await?;
+
+    let declares_html =
+        content_type.contains("text/html") || content_type.contains("application/xhtml+xml");
+    if check_empty_html
+        && declares_html
+        && body.len() < MIN_HTML_BODY_LEN
+        && !body.to_ascii_lowercase().contains("<html")
+    {
+        return Err(AppError::EmptyResponse(
+            "The page appears to be empty or blocked".to_string(),
+        ));
+    }
+
+    if accepts(&content_type, &body) {
+        return Ok(body);
+    }
+
+    Err(AppError::InvalidContentType(format!(
+        "expected {expected}, got content-type '{content_type}'"
+    )))
+}
+
+/// Reads `response`'s body in chunks, aborting as soon as the accumulated
+/// size exceeds [`MAX_RESPONSE_SIZE`] rather than buffering the whole thing
+/// first via [`reqwest::Response::text`]. A declared `Content-Length` over
+/// the cap is already rejected in [`fetch_once`] before this is ever
+/// called, but a chunked response omits that header entirely, so without
+/// this a malicious or misbehaving server could stream gigabytes and only
+/// get caught after it was all sitting in memory.
+async fn read_body_within_size_limit(response: reqwest::Response) -> Result<String, AppError> {
+    let mut bytes = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() as u64 > MAX_RESPONSE_SIZE {
+            return Err(AppError::ResponseTooLarge(format!(
+                "response exceeded the {MAX_RESPONSE_SIZE} byte cap while streaming"
+            )));
+        }
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|e| AppError::Internal(format!("response was not valid UTF-8: {e}")))
+}
+
+/// Only network-level failures and server errors are worth retrying — a 404
+/// or a page that simply isn't a recipe will look exactly the same on the
+/// next attempt.
+fn is_retryable(err: &AppError) -> bool {
+    match err {
+        AppError::Fetch(e) => {
+            e.is_timeout() || e.is_connect() || e.status().is_some_and(|s| s.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+/// Computes the delay before retry attempt `attempt` (0-indexed), via
+/// exponential backoff with ["full jitter"][1]: uniformly random between
+/// zero and `min(cap, base * 2^attempt)`. Capping the delay means a flaky
+/// server doesn't leave a bulk import waiting minutes between attempts, and
+/// the jitter means many concurrent fetches hitting the same outage don't
+/// all retry in lockstep and re-hammer it the moment it comes back.
+///
+/// Pure and deterministic given `jitter_seed`, so it's unit-testable
+/// without sleeping or depending on wall-clock randomness; [`fetch_url`]'s
+/// retry loop seeds it from the current time instead (see
+/// [`retry_jitter_seed`]).
+///
+/// [1]: https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/
+pub(crate) fn backoff_delay(
+    attempt: u32,
+    base: Duration,
+    cap: Duration,
+    jitter_seed: u64,
+) -> Duration {
+    let multiplier = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+    let exponential = base.checked_mul(multiplier).unwrap_or(cap);
+    let capped = exponential.min(cap);
+
+    // Top 53 bits of a splitmix64 output, scaled into [0, 1) — enough
+    // precision for a delay fraction, with no need for a `rand` dependency.
+    let fraction = (splitmix64(jitter_seed) >> 11) as f64 / (1u64 << 53) as f64;
+    capped.mul_f64(fraction)
+}
+
+/// A fresh seed for [`backoff_delay`]'s jitter on each real retry, mixing in
+/// the current time so concurrent fetches (which can't share a counter
+/// without coordinating) don't land on the same delay.
+fn retry_jitter_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// A small, fast, non-cryptographic PRNG step — see
+/// <https://prng.di.unimi.it/splitmix64.c> — used to turn a `u64` seed into
+/// a well-distributed `u64` for [`backoff_delay`]'s jitter.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Browser-like fallback user agent for [`download_image`] — some image
+/// CDNs 403 a plain HTTP client even though the recipe page that
+/// referenced the image imported just fine.
+const IMAGE_FALLBACK_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
+     AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+
+/// What came of trying to download a recipe's image.
+#[derive(Debug, PartialEq)]
+pub enum ImageDownloadOutcome {
+    /// The image bytes, ready to be saved.
+    Downloaded(Vec<u8>),
+    /// Downloading failed even after retrying with
+    /// [`IMAGE_FALLBACK_USER_AGENT`]; the original remote URL, so the
+    /// caller can keep linking to it directly rather than storing a path
+    /// to nothing.
+    Fallback(String),
+}
+
+/// Downloads the image at `url`. A 403 is retried once with
+/// [`IMAGE_FALLBACK_USER_AGENT`] before giving up; that retry failing, or
+/// any other failure, falls back to [`ImageDownloadOutcome::Fallback`]
+/// rather than erroring the whole import over a missing thumbnail.
+pub async fn download_image(url: &str) -> Result<ImageDownloadOutcome, AppError> {
+    match fetch_image_bytes(url, None).await {
+        Ok(bytes) => return Ok(ImageDownloadOutcome::Downloaded(bytes)),
+        Err(err) if is_forbidden(&err) => {
+            if let Ok(bytes) = fetch_image_bytes(url, Some(IMAGE_FALLBACK_USER_AGENT)).await {
+                return Ok(ImageDownloadOutcome::Downloaded(bytes));
+            }
+        }
+        Err(_) => {}
+    }
+
+    Ok(ImageDownloadOutcome::Fallback(url.to_string()))
+}
+
+async fn fetch_image_bytes(url: &str, user_agent: Option<&str>) -> Result<Vec<u8>, AppError> {
+    let client = reqwest::Client::new();
+    let mut request = client.get(url);
+    if let Some(user_agent) = user_agent {
+        request = request.header(reqwest::header::USER_AGENT, user_agent);
+    }
+
+    let response = request.send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+fn is_forbidden(err: &AppError) -> bool {
+    matches!(err, AppError::Fetch(e) if e.status() == Some(reqwest::StatusCode::FORBIDDEN))
+}
+
+/// Heuristic HTML sniff for servers that send the wrong `Content-Type`:
+/// true when the (trimmed, case-insensitive) body starts with a doctype or
+/// `<html>` tag, or otherwise contains a `<head` tag.
+fn sniffs_as_html(body: &str) -> bool {
+    let trimmed = body.trim_start().to_ascii_lowercase();
+    trimmed.starts_with("<!doctype html")
+        || trimmed.starts_with("<html")
+        || trimmed.contains("<head")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn accepts_html_served_as_text_plain() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string(
+                        "<!doctype html><html><head></head><body>Recipe</body></html>",
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let body = fetch_url(&format!("{}/recipe", server.uri()))
+            .await
+            .unwrap();
+        assert!(body.contains("Recipe"));
+    }
+
+    #[tokio::test]
+    async fn rejects_json_served_as_text_plain() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string(r#"{"not":"html"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let result = fetch_url(&format!("{}/data", server.uri())).await;
+        assert!(matches!(result, Err(AppError::InvalidContentType(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_response_declaring_a_size_over_the_cap_without_reading_the_body() {
+        let server = MockServer::start().await;
+        let oversized_body = "a".repeat(MAX_RESPONSE_SIZE as usize + 1);
+        Mock::given(method("GET"))
+            .and(path("/huge"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html")
+                    .set_body_string(oversized_body),
+            )
+            .mount(&server)
+            .await;
+
+        let result = fetch_url(&format!("{}/huge", server.uri())).await;
+        assert!(matches!(result, Err(AppError::ResponseTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_tiny_non_html_200_body_as_an_empty_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/blocked"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("access denied", "text/html"))
+            .mount(&server)
+            .await;
+
+        let result = fetch_url(&format!("{}/blocked", server.uri())).await;
+        assert!(matches!(result, Err(AppError::EmptyResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn accepts_a_normal_sized_html_body() {
+        let server = MockServer::start().await;
+        let body = format!(
+            "<!doctype html><html><head></head><body>{}</body></html>",
+            "Recipe content ".repeat(30)
+        );
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/html"))
+            .mount(&server)
+            .await;
+
+        let body = fetch_url(&format!("{}/recipe", server.uri()))
+            .await
+            .unwrap();
+        assert!(body.contains("Recipe content"));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_chunked_response_over_the_cap_without_fully_buffering_it() {
+        let server = MockServer::start().await;
+        let oversized_body = "a".repeat(MAX_RESPONSE_SIZE as usize + 1);
+        Mock::given(method("GET"))
+            .and(path("/chunked"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html")
+                    .insert_header("transfer-encoding", "chunked")
+                    .set_body_string(oversized_body),
+            )
+            .mount(&server)
+            .await;
+
+        let result = fetch_url(&format!("{}/chunked", server.uri())).await;
+        assert!(matches!(result, Err(AppError::ResponseTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn retries_a_transient_server_error_and_then_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html")
+                    .set_body_string("<html><body>Recovered</body></html>"),
+            )
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let body = fetch_url(&format!("{}/flaky", server.uri())).await.unwrap();
+        assert!(body.contains("Recovered"));
+    }
+
+    #[tokio::test]
+    async fn a_persistent_server_error_is_not_retried_forever() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/down"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let result = fetch_url(&format!("{}/down", server.uri())).await;
+        assert!(matches!(result, Err(AppError::Fetch(_))));
+    }
+
+    #[test]
+    fn backoff_delay_grows_with_the_attempt_number_for_a_fixed_seed() {
+        let base = Duration::from_millis(250);
+        let cap = Duration::from_secs(10);
+        let seed = 42;
+
+        let first = backoff_delay(0, base, cap, seed);
+        let second = backoff_delay(1, base, cap, seed);
+        let third = backoff_delay(2, base, cap, seed);
+
+        assert!(first < second);
+        assert!(second < third);
+    }
+
+    #[test]
+    fn backoff_delay_never_exceeds_the_cap() {
+        let base = Duration::from_millis(250);
+        let cap = Duration::from_secs(10);
+
+        for attempt in 0..20 {
+            for seed in [0, 1, 42, u64::MAX, 123_456_789] {
+                let delay = backoff_delay(attempt, base, cap, seed);
+                assert!(
+                    delay <= cap,
+                    "attempt {attempt} seed {seed} exceeded cap: {delay:?}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn download_image_retries_a_403_with_a_browser_like_user_agent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/image.jpg"))
+            .and(wiremock::matchers::header_regex("user-agent", "Chrome"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg-bytes".to_vec()))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/image.jpg"))
+            .respond_with(ResponseTemplate::new(403))
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let outcome = download_image(&format!("{}/image.jpg", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            ImageDownloadOutcome::Downloaded(b"jpeg-bytes".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn download_image_falls_back_to_the_url_on_a_persistent_403() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/image.jpg"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/image.jpg", server.uri());
+        let outcome = download_image(&url).await.unwrap();
+
+        assert_eq!(outcome, ImageDownloadOutcome::Fallback(url));
+    }
+
+    #[tokio::test]
+    async fn fetch_url_with_client_uses_the_injected_clients_user_agent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("content-type", "text/html").set_body_string(
+                    "<!doctype html><html><head></head><body>Recipe</body></html>",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::builder()
+            .user_agent("feast-test-agent/1.0")
+            .build()
+            .unwrap();
+
+        fetch_url_with_client(&client, &format!("{}/recipe", server.uri()))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0]
+                .headers
+                .get("user-agent")
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "feast-test-agent/1.0"
+        );
+    }
+
+    #[test]
+    fn backoff_delay_varies_with_the_jitter_seed() {
+        let base = Duration::from_millis(250);
+        let cap = Duration::from_secs(10);
+
+        let a = backoff_delay(5, base, cap, 1);
+        let b = backoff_delay(5, base, cap, 2);
+
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,797 @@
+pub mod fetch;
+
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::{AppError, ErrorResponse};
+use crate::importer::fetch::{fetch_json_url, fetch_url};
+use crate::models::{Recipe, RecipeIngredientExport, RecipeInput, DIFFICULTIES};
+use crate::parser::error::ParseError;
+use crate::parser::links::extract_canonical_url;
+use crate::parser::recipe::{
+    extract_f64, extract_first_integer, extract_image_url, extract_tags, find_recipe_object,
+    parse_confidence, parse_instructions, parse_recipe_html, parse_recipe_html_blocking,
+    parse_recipe_json, parse_servings, sanitize_text, ImportConfidence, ParsedRecipe,
+};
+use crate::utils::units::{extract_prep_note, parse_measurement};
+
+/// Fetches `url` and parses it as a recipe. Category pages and AMP mirrors
+/// often have no JSON-LD of their own but point at the real recipe page
+/// through a `<link rel="canonical">` or `rel="amphtml">` tag, so when the
+/// first fetch turns up no JSON-LD at all, this follows that link once and
+/// retries. A canonical link that just points back at `url` is ignored
+/// rather than followed, so a page that canonicalizes to itself can't loop;
+/// a second hop is never attempted either way.
+///
+/// Rejects `url` up front if it isn't a valid `http(s)` URL, and if a
+/// recipe has already been imported from it, before spending a network
+/// round trip on either — see [`import_error_code`] for how callers
+/// distinguish these from a fetch or parse failure.
+///
+/// A URL ending in `.json` is treated as the JSON escape hatch some
+/// client-rendered recipe apps expose in place of JSON-LD: the body is
+/// fetched via [`fetch_json_url`] and fed straight into
+/// [`parse_recipe_json`], since there's no HTML to scrape it out of.
+pub async fn import_recipe_from_url(
+    pool: &SqlitePool,
+    url: &str,
+) -> Result<ParsedRecipe, AppError> {
+    let https_only = db::settings::get_https_only(pool).await?;
+    validate_url(url, https_only)?;
+    if db::recipes::recipe_exists_by_source_url(pool, url).await? {
+        return Err(AppError::Conflict(format!(
+            "a recipe from {url} has already been imported"
+        )));
+    }
+
+    if url.to_ascii_lowercase().ends_with(".json") {
+        let body = fetch_json_url(url).await?;
+        let value: Value =
+            serde_json::from_str(&body).map_err(|e| ParseError::MalformedJson(e.to_string()))?;
+        return parse_recipe_json(&value).map_err(AppError::from);
+    }
+
+    let html = fetch_url(url).await?;
+
+    match parse_recipe_html_blocking(html.clone()).await {
+        Err(AppError::Parse(ParseError::NoJsonLd)) => match extract_canonical_url(&html, url) {
+            Some(canonical_url) if canonical_url != url => {
+                validate_url(&canonical_url, https_only)?;
+                let canonical_html = fetch_url(&canonical_url).await?;
+                parse_recipe_html_blocking(canonical_html).await
+            }
+            _ => Err(ParseError::NoJsonLd.into()),
+        },
+        result => result,
+    }
+}
+
+/// Reads a JSON-LD recipe's `recipeDifficulty` (or the less common bare
+/// `difficulty`) and matches it case-insensitively against [`DIFFICULTIES`],
+/// returning the canonically-cased value. A value outside that set is
+/// dropped rather than passed through, since [`validate_difficulty`] would
+/// otherwise reject the whole import over a site's idiosyncratic wording.
+///
+/// [`validate_difficulty`]: crate::db::recipes::validate_difficulty
+fn extract_difficulty(recipe: &Value) -> Option<String> {
+    let raw = recipe
+        .get("recipeDifficulty")
+        .or_else(|| recipe.get("difficulty"))
+        .and_then(Value::as_str)?;
+    DIFFICULTIES
+        .iter()
+        .find(|d| d.eq_ignore_ascii_case(raw))
+        .map(|d| d.to_string())
+}
+
+/// Rejects anything that isn't a valid `http(s)` URL. When `https_only` is
+/// set (see [`db::settings::get_https_only`]), a well-formed `http` URL is
+/// rejected too, for deployments that want to forbid plain-text imports
+/// entirely.
+fn validate_url(url: &str, https_only: bool) -> Result<(), AppError> {
+    match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "https" => Ok(()),
+        Ok(parsed) if parsed.scheme() == "http" && !https_only => Ok(()),
+        Ok(parsed) if parsed.scheme() == "http" => Err(AppError::Validation(format!(
+            "'{url}' uses http, but this deployment only allows https imports"
+        ))),
+        _ => Err(AppError::Validation(format!("'{url}' is not a valid URL"))),
+    }
+}
+
+/// What a pre-import probe of a URL found, for a paste-a-URL UI to preview
+/// before committing to a full [`import_recipe_from_url`] — see
+/// [`check_import_url`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportCheck {
+    pub reachable: bool,
+    pub is_duplicate: bool,
+    pub likely_recipe: bool,
+    pub reason: Option<String>,
+    /// [`ImportConfidence::from_score`] of [`parse_confidence`] for the
+    /// recipe [`parse_recipe_html`] found, or `None` when `likely_recipe`
+    /// is `false` — there's no parsed result to score in that case.
+    pub confidence: Option<ImportConfidence>,
+}
+
+/// Fetches `url` once and reports whether it's reachable, already imported,
+/// and looks like it carries recipe JSON-LD — without doing the full
+/// ingredient/instruction extraction [`import_recipe_from_url`] would, so a
+/// UI can validate a pasted URL before the user commits to an import.
+///
+/// `is_duplicate` is checked even when the fetch fails, since it's a free
+/// local lookup; `likely_recipe` is only ever true when `reachable` is,
+/// since there's nothing to sniff otherwise. `reason` carries the fetch
+/// error when unreachable, or the parse error when reachable but not a
+/// recipe — `None` once the three booleans already say everything there is
+/// to say.
+pub async fn check_import_url(pool: &SqlitePool, url: &str) -> Result<ImportCheck, AppError> {
+    let https_only = db::settings::get_https_only(pool).await?;
+    validate_url(url, https_only)?;
+    let is_duplicate = db::recipes::recipe_exists_by_source_url(pool, url).await?;
+
+    let html = match fetch_url(url).await {
+        Ok(html) => html,
+        Err(err) => {
+            return Ok(ImportCheck {
+                reachable: false,
+                is_duplicate,
+                likely_recipe: false,
+                reason: Some(err.to_string()),
+                confidence: None,
+            });
+        }
+    };
+
+    let (likely_recipe, reason, confidence) = match parse_recipe_html(&html) {
+        Ok(parsed) => (
+            true,
+            None,
+            Some(ImportConfidence::from_score(parse_confidence(&parsed))),
+        ),
+        Err(ParseError::NoJsonLd) if extract_canonical_url(&html, url).is_some() => {
+            (true, None, None)
+        }
+        Err(err) => (false, Some(err.to_string()), None),
+    };
+
+    Ok(ImportCheck {
+        reachable: true,
+        is_duplicate,
+        likely_recipe,
+        reason,
+        confidence,
+    })
+}
+
+/// A structured error code for [`import_recipe_from_url`] failures, finer
+/// grained than [`ErrorResponse`]'s generic codes: the frontend needs to
+/// tell a bad URL apart from a timed-out fetch apart from a page with no
+/// recipe, so it can offer a different recovery action for each rather
+/// than just showing the message text.
+pub fn import_error_code(err: &AppError) -> &'static str {
+    match err {
+        AppError::NotFound(_) => "NOT_FOUND",
+        AppError::Validation(_) => "INVALID_URL",
+        AppError::Conflict(_) => "DUPLICATE",
+        AppError::Database(_) | AppError::Migration(_) => "DATABASE",
+        AppError::Fetch(e) if e.is_timeout() => "FETCH_TIMEOUT",
+        AppError::Fetch(_) => "FETCH_FAILED",
+        AppError::InvalidContentType(_) => "NO_RECIPE",
+        AppError::ResponseTooLarge(_) => "FETCH_FAILED",
+        AppError::EmptyResponse(_) => "EMPTY_RESPONSE",
+        AppError::Parse(ParseError::NoJsonLd | ParseError::NotARecipe) => "NO_RECIPE",
+        AppError::Parse(ParseError::MultipleRecipes) => "MULTIPLE_RECIPES",
+        AppError::Parse(ParseError::MalformedJson(_)) => "PARSE_FAILED",
+        AppError::Parse(ParseError::TooLarge) => "FETCH_FAILED",
+        AppError::Internal(_) => "INTERNAL",
+    }
+}
+
+/// Serializes `err` into the `{code, message}` shape the frontend expects
+/// from a failed [`import_recipe_from_url`] call, using [`import_error_code`]
+/// in place of [`ErrorResponse`]'s generic code.
+pub fn import_error_response(err: &AppError) -> String {
+    let response = ErrorResponse {
+        code: import_error_code(err).to_string(),
+        message: err.to_string(),
+    };
+    let message = response.message.clone();
+    serde_json::to_string(&response).unwrap_or(message)
+}
+
+/// Creates a recipe straight from a pasted JSON-LD `Recipe` object, for
+/// advanced users who have copied it out of a site's source rather than
+/// the page's full HTML. `jsonld` may be the bare Recipe object, an array
+/// of nodes, or a `@graph`-wrapped document — the same shapes
+/// [`parse_recipe_html`] already understands. Ingredient lines are split
+/// into a leading quantity and a free-text remainder via
+/// [`parse_measurement`]; there's no reliable way to further separate a
+/// unit from the ingredient name out of arbitrary site text, so the whole
+/// remainder is kept as the ingredient's name and its `unit` is left blank.
+/// A trailing prep clause in that remainder (`"garlic (minced)"`) is pulled
+/// out via [`extract_prep_note`] into the ingredient's `notes` instead of
+/// staying stuck in its name.
+pub async fn import_recipe_from_jsonld(
+    pool: &SqlitePool,
+    jsonld: &str,
+    source_url: Option<String>,
+) -> Result<Recipe, AppError> {
+    let value: Value =
+        serde_json::from_str(jsonld).map_err(|e| ParseError::MalformedJson(e.to_string()))?;
+    let recipe = find_recipe_object(&value)?.ok_or(ParseError::NotARecipe)?;
+
+    let name = sanitize_text(
+        recipe
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("Untitled Recipe"),
+    );
+    let description = recipe
+        .get("description")
+        .and_then(Value::as_str)
+        .map(sanitize_text);
+    let (servings, yield_unit) = recipe
+        .get("recipeYield")
+        .map(parse_servings)
+        .unwrap_or((None, None));
+    let servings = servings.unwrap_or(4);
+    let instructions = parse_instructions(recipe);
+    let image_path = recipe.get("image").and_then(extract_image_url);
+    let aggregate_rating = recipe.get("aggregateRating");
+    let rating_value = aggregate_rating
+        .and_then(|r| r.get("ratingValue"))
+        .and_then(extract_f64);
+    let rating_count = aggregate_rating
+        .and_then(|r| r.get("reviewCount").or_else(|| r.get("ratingCount")))
+        .and_then(extract_first_integer);
+    let difficulty = extract_difficulty(recipe);
+
+    let created = db::recipes::create_recipe(
+        pool,
+        RecipeInput {
+            name,
+            description,
+            servings,
+            yield_unit,
+            prep_time: None,
+            cook_time: None,
+            instructions,
+            image_path,
+            source_url,
+            notes: None,
+            rating_value,
+            rating_count,
+            difficulty,
+            yield_notes: None,
+        },
+    )
+    .await?;
+
+    let ingredient_lines = recipe
+        .get("recipeIngredient")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_else(Vec::<&str>::new);
+
+    for (sort_order, line) in ingredient_lines.into_iter().enumerate() {
+        let (quantity, name, _package_size) = parse_measurement(line);
+        let (name, notes) = extract_prep_note(&name);
+        db::recipes::add_recipe_ingredient(
+            pool,
+            &created.id,
+            &RecipeIngredientExport {
+                name,
+                quantity,
+                unit: String::new(),
+                notes,
+                sort_order: sort_order as i64,
+            },
+            None,
+        )
+        .await?;
+    }
+
+    for tag in extract_tags(recipe) {
+        db::recipes::add_recipe_tag(pool, &created.id, &tag).await?;
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn follows_a_canonical_link_to_a_page_with_a_recipe() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/category"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<html><head><link rel="canonical" href="{}/recipe"></head></html>"#,
+                server.uri()
+            )))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><script type="application/ld+json">
+                    {"@type": "Recipe", "name": "Canonical Soup"}
+                </script></head></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let recipe = import_recipe_from_url(&pool, &format!("{}/category", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(recipe.name, "Canonical Soup");
+    }
+
+    #[tokio::test]
+    async fn a_canonical_link_pointing_back_at_itself_does_not_loop() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+        let url = format!("{}/category", server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/category"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<html><head><link rel="canonical" href="{url}"></head></html>"#
+            )))
+            .mount(&server)
+            .await;
+
+        let result = import_recipe_from_url(&pool, &url).await;
+        assert!(matches!(result, Err(AppError::Parse(ParseError::NoJsonLd))));
+    }
+
+    #[tokio::test]
+    async fn https_only_rejects_an_http_url() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        db::settings::set_setting(&pool, "https_only", "true")
+            .await
+            .unwrap();
+
+        let result = import_recipe_from_url(&pool, "http://example.com/recipe").await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert_eq!(import_error_code(&result.unwrap_err()), "INVALID_URL");
+    }
+
+    #[test]
+    fn validate_url_with_https_only_rejects_http() {
+        let result = validate_url("http://example.com/recipe", true);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn validate_url_with_https_only_allows_https() {
+        let result = validate_url("https://example.com/recipe", true);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_url_without_https_only_allows_both_schemes() {
+        assert!(validate_url("http://example.com/recipe", false).is_ok());
+        assert!(validate_url("https://example.com/recipe", false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_an_invalid_url_before_making_any_request() {
+        let pool = crate::db::pool::init_db_for_test().await;
+
+        let result = import_recipe_from_url(&pool, "not-a-url").await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert_eq!(import_error_code(&result.unwrap_err()), "INVALID_URL");
+    }
+
+    #[tokio::test]
+    async fn imports_a_recipe_from_a_json_endpoint() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/recipe.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/json")
+                    .set_body_string(r#"{"@type": "Recipe", "name": "JSON Endpoint Soup"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let recipe = import_recipe_from_url(&pool, &format!("{}/recipe.json", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(recipe.name, "JSON Endpoint Soup");
+    }
+
+    #[tokio::test]
+    async fn rejects_non_recipe_json_from_a_json_endpoint() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/not-a-recipe.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/json")
+                    .set_body_string(r#"{"@type": "Thing", "name": "Just a thing"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let result =
+            import_recipe_from_url(&pool, &format!("{}/not-a-recipe.json", server.uri())).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::Parse(ParseError::NotARecipe))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_url_already_imported_as_a_duplicate() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+        let url = format!("{}/recipe", server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><script type="application/ld+json">
+                    {"@type": "Recipe", "name": "Already Imported Soup"}
+                </script></head></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        import_recipe_from_jsonld(
+            &pool,
+            r#"{"@type": "Recipe", "name": "Already Imported Soup"}"#,
+            Some(url.clone()),
+        )
+        .await
+        .unwrap();
+
+        let result = import_recipe_from_url(&pool, &url).await;
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert_eq!(import_error_code(&result.unwrap_err()), "DUPLICATE");
+    }
+
+    #[tokio::test]
+    async fn a_page_with_more_than_one_recipe_is_reported_as_ambiguous() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/roundup"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><script type="application/ld+json">
+                    {"@graph": [
+                        {"@type": "Recipe", "name": "Roundup One"},
+                        {"@type": "Recipe", "name": "Roundup Two"}
+                    ]}
+                </script></head></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let result = import_recipe_from_url(&pool, &format!("{}/roundup", server.uri())).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::Parse(ParseError::MultipleRecipes))
+        ));
+        assert_eq!(import_error_code(&result.unwrap_err()), "MULTIPLE_RECIPES");
+    }
+
+    #[tokio::test]
+    async fn a_timed_out_fetch_is_reported_as_fetch_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let err: AppError = client
+            .get(format!("{}/slow", server.uri()))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+
+        assert!(matches!(err, AppError::Fetch(ref e) if e.is_timeout()));
+        assert_eq!(import_error_code(&err), "FETCH_TIMEOUT");
+    }
+
+    #[test]
+    fn no_recipe_and_parse_failed_codes_are_distinguished_from_each_other() {
+        assert_eq!(
+            import_error_code(&AppError::Parse(ParseError::NoJsonLd)),
+            "NO_RECIPE"
+        );
+        assert_eq!(
+            import_error_code(&AppError::Parse(ParseError::NotARecipe)),
+            "NO_RECIPE"
+        );
+        assert_eq!(
+            import_error_code(&AppError::Parse(ParseError::MalformedJson(
+                "bad".to_string()
+            ))),
+            "PARSE_FAILED"
+        );
+    }
+
+    #[tokio::test]
+    async fn imports_a_recipe_from_a_pasted_jsonld_object() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Pasted JSON-LD Chili",
+            "recipeYield": "6 servings",
+            "recipeIngredient": ["2 cups kidney beans", "1 lb ground beef"],
+            "recipeInstructions": ["Brown the beef.", "Simmer with beans."]
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(
+            &pool,
+            jsonld,
+            Some("https://example.com/pasted-chili".to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(recipe.name, "Pasted JSON-LD Chili");
+        assert_eq!(
+            recipe.source_url,
+            Some("https://example.com/pasted-chili".to_string())
+        );
+
+        let ingredients = db::recipes::get_recipe_ingredients(&pool, &recipe.id)
+            .await
+            .unwrap();
+        assert_eq!(ingredients.len(), 2);
+        assert_eq!(ingredients[0].name, "cups kidney bean");
+        assert_eq!(ingredients[0].quantity, 2.0);
+    }
+
+    #[tokio::test]
+    async fn a_pasted_jsonlds_categories_and_cuisines_become_tags() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Tagged JSON-LD Curry",
+            "recipeCategory": ["Dinner", "Main Course"],
+            "recipeCuisine": "Thai",
+            "keywords": "spicy, weeknight"
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(&pool, jsonld, None)
+            .await
+            .unwrap();
+
+        let mut tags = db::recipes::get_recipe_tags(&pool, &recipe.id).await.unwrap();
+        tags.sort();
+        assert_eq!(
+            tags,
+            vec![
+                "Dinner".to_string(),
+                "Main Course".to_string(),
+                "Thai".to_string(),
+                "spicy".to_string(),
+                "weeknight".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_pasted_ingredients_prep_clause_survives_into_the_saved_recipe() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Prep Note JSON-LD Soup",
+            "recipeIngredient": ["2 cloves garlic (minced)", "1 cup broth"]
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(&pool, jsonld, None)
+            .await
+            .unwrap();
+
+        let saved = db::recipes::get_recipe_by_id(&pool, &recipe.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.name, "Prep Note JSON-LD Soup");
+
+        let ingredients = db::recipes::get_recipe_ingredients(&pool, &recipe.id)
+            .await
+            .unwrap();
+        let garlic = ingredients
+            .iter()
+            .find(|i| i.name.contains("garlic"))
+            .unwrap();
+        assert_eq!(garlic.notes, Some("minced".to_string()));
+
+        let broth = ingredients
+            .iter()
+            .find(|i| i.name.contains("broth"))
+            .unwrap();
+        assert_eq!(broth.notes, None);
+    }
+
+    #[tokio::test]
+    async fn imports_a_pasted_jsonlds_recipe_difficulty_case_insensitively() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Difficulty JSON-LD Chili",
+            "recipeDifficulty": "medium"
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(&pool, jsonld, None)
+            .await
+            .unwrap();
+
+        assert_eq!(recipe.difficulty, Some("Medium".to_string()));
+    }
+
+    #[tokio::test]
+    async fn ignores_a_pasted_jsonlds_unrecognized_difficulty() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Unrecognized Difficulty JSON-LD Chili",
+            "recipeDifficulty": "Gourmet"
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(&pool, jsonld, None)
+            .await
+            .unwrap();
+
+        assert_eq!(recipe.difficulty, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_pasted_jsonld_object_that_is_not_a_recipe() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{"@type": "Article", "name": "Not A Recipe"}"#;
+
+        let result = import_recipe_from_jsonld(&pool, jsonld, None).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::Parse(ParseError::NotARecipe))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_jsonld() {
+        let pool = crate::db::pool::init_db_for_test().await;
+
+        let result = import_recipe_from_jsonld(&pool, "{not valid json", None).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::Parse(ParseError::MalformedJson(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn strips_control_characters_from_a_pasted_recipes_name_and_description() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let jsonld = r#"{
+            "@type": "Recipe",
+            "name": "Dirty\u0000 Chili",
+            "description": "A family\u000b favorite."
+        }"#;
+
+        let recipe = import_recipe_from_jsonld(&pool, jsonld, None)
+            .await
+            .unwrap();
+
+        assert_eq!(recipe.name, "Dirty Chili");
+        assert_eq!(recipe.description, Some("A family favorite.".to_string()));
+    }
+
+    #[tokio::test]
+    async fn check_import_url_reports_a_valid_recipe_page_as_likely() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><script type="application/ld+json">
+                    {"@type": "Recipe", "name": "Checkable Soup"}
+                </script></head></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let check = check_import_url(&pool, &format!("{}/recipe", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            check,
+            ImportCheck {
+                reachable: true,
+                is_duplicate: false,
+                likely_recipe: true,
+                reason: None,
+                confidence: Some(ImportConfidence::Low),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn check_import_url_reports_an_already_imported_url_as_a_duplicate() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+        let url = format!("{}/recipe", server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/recipe"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><script type="application/ld+json">
+                    {"@type": "Recipe", "name": "Already Checked Soup"}
+                </script></head></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        import_recipe_from_jsonld(
+            &pool,
+            r#"{"@type": "Recipe", "name": "Already Checked Soup"}"#,
+            Some(url.clone()),
+        )
+        .await
+        .unwrap();
+
+        let check = check_import_url(&pool, &url).await.unwrap();
+
+        assert!(check.is_duplicate);
+    }
+
+    #[tokio::test]
+    async fn check_import_url_reports_an_unreachable_host_as_unreachable() {
+        let pool = crate::db::pool::init_db_for_test().await;
+        let server = MockServer::start().await;
+        let dead_url = format!("{}/gone", server.uri());
+        drop(server);
+
+        let check = check_import_url(&pool, &dead_url).await.unwrap();
+
+        assert_eq!(
+            check,
+            ImportCheck {
+                reachable: false,
+                is_duplicate: false,
+                likely_recipe: false,
+                reason: check.reason.clone(),
+                confidence: None,
+            }
+        );
+        assert!(check.reason.is_some());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod backup;
+pub mod correlation;
+pub mod db;
+pub mod error;
+pub mod export;
+pub mod importer;
+pub mod logging;
+pub mod menu_export;
+pub mod models;
+pub mod parser;
+pub mod recipe_text;
+pub mod utils;
+pub mod week_view;
@@ -0,0 +1,510 @@
+//! Validates `logging.json` before it's applied. A bad level string there
+//! previously only surfaced as an `eprintln` at startup that nobody saw;
+//! [`validate_log_config`] lets a settings UI catch the mistake up front.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::redact::redact_string;
+
+/// Shape of `logging.json`: a default level plus optional per-module
+/// overrides, keyed by module path (e.g. `"feast_core::importer"`), plus
+/// whether the webview should be a log target. `include_webview` defaults
+/// to `true` since that's the shipped app's behavior; it's set to `false`
+/// by headless/CLI setups and integration tests that initialize logging
+/// without a running webview, where emitting to a nonexistent one can
+/// error or silently drop.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogConfig {
+    pub default_level: String,
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+    #[serde(default = "default_include_webview")]
+    pub include_webview: bool,
+    /// When set, name-like values (e.g. a recipe name in a frontend log's
+    /// structured `data`) are redacted down to their length instead of
+    /// shown verbatim — see [`crate::utils::redact::redact_string`]. Off by
+    /// default to preserve today's debuggability; a shared/kiosk machine is
+    /// expected to turn it on in `logging.json`.
+    #[serde(default)]
+    pub redact_content: bool,
+}
+
+fn default_include_webview() -> bool {
+    true
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            default_level: "info".to_string(),
+            modules: BTreeMap::new(),
+            include_webview: true,
+            redact_content: false,
+        }
+    }
+}
+
+/// A `LogConfig` that has been checked and is safe to apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogConfigSummary {
+    pub default_level: String,
+    pub modules: BTreeMap<String, String>,
+    pub include_webview: bool,
+    pub redact_content: bool,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum LogConfigError {
+    #[error("logging config was malformed: {0}")]
+    MalformedJson(String),
+
+    #[error("invalid log level '{value}' for '{field}'")]
+    InvalidLevel { field: String, value: String },
+
+    #[error("logging config could not be read: {0}")]
+    Unreadable(String),
+}
+
+impl LogConfig {
+    /// Reads and validates the `logging.json`-shaped file at `path`. A
+    /// missing file is not an error — it's the normal state for a fresh
+    /// install — and resolves to `Ok(`[`LogConfig::default`]`)` just like a
+    /// present-and-valid file would. A file that exists but can't be read
+    /// (permission denied, owned by another user, a directory in the way)
+    /// or can't be parsed comes back as `Err` instead, so a caller that
+    /// wants to tell those two "I'm using defaults" cases apart — as
+    /// opposed to [`load`](LogConfig::load), which can't — is able to.
+    pub fn load_result(path: &Path) -> Result<Self, LogConfigError> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(LogConfig::default());
+            }
+            Err(err) => return Err(LogConfigError::Unreadable(err.to_string())),
+        };
+
+        let summary = validate_log_config(&json)?;
+        Ok(LogConfig {
+            default_level: summary.default_level,
+            modules: summary.modules,
+            include_webview: summary.include_webview,
+            redact_content: summary.redact_content,
+        })
+    }
+
+    /// Like [`load_result`](LogConfig::load_result), but collapses any
+    /// error into [`LogConfig::default`] after reporting it with an
+    /// `eprintln` — this runs before the logging plugin itself exists, so
+    /// there's no other sink available yet. A missing file produces no
+    /// such message at all, since it isn't a problem to report.
+    pub fn load(path: &Path) -> Self {
+        Self::load_result(path).unwrap_or_else(|err| {
+            eprintln!(
+                "logging config at {} is invalid ({err}); falling back to defaults",
+                path.display()
+            );
+            LogConfig::default()
+        })
+    }
+}
+
+/// Parses a log level string (`"error"`, `"warn"`, `"info"`, `"debug"`,
+/// `"trace"`, case-insensitive) using the same vocabulary as
+/// [`log::LevelFilter`].
+pub fn parse_level(level: &str) -> Option<log::LevelFilter> {
+    level.parse().ok()
+}
+
+/// Deserializes `json` into a [`LogConfig`] and checks every level string
+/// (the default and each per-module override) with [`parse_level`],
+/// without applying any of it. Returns a [`LogConfigSummary`] on success, or
+/// a [`LogConfigError`] naming the bad field on failure.
+pub fn validate_log_config(json: &str) -> Result<LogConfigSummary, LogConfigError> {
+    let config: LogConfig =
+        serde_json::from_str(json).map_err(|e| LogConfigError::MalformedJson(e.to_string()))?;
+
+    if parse_level(&config.default_level).is_none() {
+        return Err(LogConfigError::InvalidLevel {
+            field: "default_level".to_string(),
+            value: config.default_level,
+        });
+    }
+
+    for (module, level) in &config.modules {
+        if parse_level(level).is_none() {
+            return Err(LogConfigError::InvalidLevel {
+                field: module.clone(),
+                value: level.clone(),
+            });
+        }
+    }
+
+    Ok(LogConfigSummary {
+        default_level: config.default_level,
+        modules: config.modules,
+        include_webview: config.include_webview,
+        redact_content: config.redact_content,
+    })
+}
+
+/// Maximum number of keys from a frontend log's structured `data` payload
+/// that get included in the log line — anything beyond this is dropped
+/// rather than serialized, so a large object doesn't blow log rotation
+/// budgets.
+const MAX_DATA_KEYS: usize = 20;
+
+/// Maximum length, in characters, of the serialized `data` payload before
+/// it's truncated with a `…` indicator.
+const MAX_SERIALIZED_LEN: usize = 500;
+
+/// Formats a frontend log's structured `data` map for inclusion in a log
+/// line: keeps at most [`MAX_DATA_KEYS`] keys (in `data`'s existing sorted
+/// order), redacts string values with [`redact_string`] (showing
+/// length-only when `redact_content` is set, per [`LogConfig::redact_content`]),
+/// then serializes and caps the result at [`MAX_SERIALIZED_LEN`] characters.
+/// A frontend that logs a whole recipe or API response can't turn one log
+/// line into megabytes.
+pub fn format_frontend_log_data(data: &BTreeMap<String, Value>, redact_content: bool) -> String {
+    let capped: serde_json::Map<String, Value> = data
+        .iter()
+        .take(MAX_DATA_KEYS)
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => Value::String(redact_string(s, redact_content)),
+                other => other.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+
+    let serialized = serde_json::to_string(&capped).unwrap_or_default();
+    match serialized.char_indices().nth(MAX_SERIALIZED_LEN) {
+        Some((boundary, _)) => format!("{}…", &serialized[..boundary]),
+        None => serialized,
+    }
+}
+
+/// Maximum number of entries [`tail_log_entries`] will ever return,
+/// regardless of what a caller asks for — a guard against a huge `lines`
+/// value (an off-by-several-orders-of-magnitude frontend bug, or just a
+/// user typing a big number) reading an unbounded amount of the log file
+/// into memory.
+pub const MAX_LOG_TAIL_LINES: usize = 1000;
+
+/// Reads the last `lines` JSON log entries from the log file at `path`,
+/// keeping only entries at or more severe than `min_level` when one is
+/// given. `lines` is capped at [`MAX_LOG_TAIL_LINES`] first, before
+/// filtering, so a generous `lines` request against a mostly-`debug` log
+/// still can't pull the whole file into memory.
+///
+/// A missing file is not an error — it's the normal state before the app
+/// has logged anything yet — and resolves to an empty vec, matching
+/// [`LogConfig::load_result`]'s treatment of a missing `logging.json`.
+/// Lines that aren't valid JSON (a partially-written line, a stray blank
+/// line at EOF) are skipped rather than failing the whole read.
+pub fn tail_log_entries(path: &Path, lines: usize, min_level: Option<&str>) -> Vec<Value> {
+    let lines = lines.min(MAX_LOG_TAIL_LINES);
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    let min_level = min_level.and_then(|level| level.parse::<log::Level>().ok());
+
+    let entries: Vec<Value> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|entry| {
+            let Some(min_level) = min_level else {
+                return true;
+            };
+            entry
+                .get("level")
+                .and_then(Value::as_str)
+                .and_then(|level| level.parse::<log::Level>().ok())
+                .is_some_and(|level| level <= min_level)
+        })
+        .collect();
+
+    let skip = entries.len().saturating_sub(lines);
+    entries[skip..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_a_valid_config() {
+        let json = r#"{
+            "default_level": "info",
+            "modules": { "feast_core::importer": "debug" }
+        }"#;
+
+        let summary = validate_log_config(json).unwrap();
+
+        assert_eq!(summary.default_level, "info");
+        assert_eq!(
+            summary.modules.get("feast_core::importer"),
+            Some(&"debug".to_string())
+        );
+        assert!(summary.include_webview);
+    }
+
+    #[test]
+    fn include_webview_defaults_to_true_when_omitted() {
+        let summary = validate_log_config(r#"{"default_level": "info"}"#).unwrap();
+        assert!(summary.include_webview);
+    }
+
+    #[test]
+    fn include_webview_can_be_turned_off() {
+        let summary =
+            validate_log_config(r#"{"default_level": "info", "include_webview": false}"#).unwrap();
+        assert!(!summary.include_webview);
+    }
+
+    #[test]
+    fn redact_content_defaults_to_false_when_omitted() {
+        let summary = validate_log_config(r#"{"default_level": "info"}"#).unwrap();
+        assert!(!summary.redact_content);
+    }
+
+    #[test]
+    fn redact_content_can_be_turned_on() {
+        let summary =
+            validate_log_config(r#"{"default_level": "info", "redact_content": true}"#).unwrap();
+        assert!(summary.redact_content);
+    }
+
+    #[test]
+    fn rejects_an_invalid_level_string() {
+        let json = r#"{
+            "default_level": "info",
+            "modules": { "feast_core::importer": "very loud" }
+        }"#;
+
+        let err = validate_log_config(json).unwrap_err();
+
+        assert_eq!(
+            err,
+            LogConfigError::InvalidLevel {
+                field: "feast_core::importer".to_string(),
+                value: "very loud".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        let err = validate_log_config("{ not json").unwrap_err();
+        assert!(matches!(err, LogConfigError::MalformedJson(_)));
+    }
+
+    #[test]
+    fn load_result_of_an_absent_file_is_the_default_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+
+        let config = LogConfig::load_result(&path).unwrap();
+
+        assert_eq!(config.default_level, LogConfig::default().default_level);
+        assert!(config.modules.is_empty());
+    }
+
+    #[test]
+    fn load_of_an_absent_file_is_the_default_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+
+        let config = LogConfig::load(&path);
+
+        assert_eq!(config.default_level, LogConfig::default().default_level);
+    }
+
+    #[test]
+    fn load_result_of_a_present_and_valid_file_returns_the_parsed_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+        std::fs::write(&path, r#"{"default_level": "debug"}"#).unwrap();
+
+        let config = LogConfig::load_result(&path).unwrap();
+
+        assert_eq!(config.default_level, "debug");
+    }
+
+    #[test]
+    fn load_result_of_a_present_but_malformed_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let err = LogConfig::load_result(&path).unwrap_err();
+
+        assert!(matches!(err, LogConfigError::MalformedJson(_)));
+    }
+
+    #[test]
+    fn load_of_a_present_but_malformed_file_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let config = LogConfig::load(&path);
+
+        assert_eq!(config.default_level, LogConfig::default().default_level);
+    }
+
+    #[test]
+    fn load_result_of_an_unreadable_file_is_an_unreadable_error() {
+        // A directory where the file is expected can't be read as a
+        // string, giving a non-`NotFound` I/O error without needing to
+        // fiddle with permission bits that wouldn't be portable anyway.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logging.json");
+        std::fs::create_dir(&path).unwrap();
+
+        let err = LogConfig::load_result(&path).unwrap_err();
+
+        assert!(matches!(err, LogConfigError::Unreadable(_)));
+    }
+
+    #[test]
+    fn a_small_data_map_passes_through_intact() {
+        let mut data = BTreeMap::new();
+        data.insert(
+            "recipe_id".to_string(),
+            Value::String("abc-123".to_string()),
+        );
+        data.insert("servings".to_string(), Value::from(4));
+
+        let formatted = format_frontend_log_data(&data, false);
+
+        assert_eq!(
+            formatted,
+            r#"{"recipe_id":"abc-123","servings":4}"#.to_string()
+        );
+    }
+
+    #[test]
+    fn with_redact_content_a_small_data_maps_strings_become_length_only() {
+        let mut data = BTreeMap::new();
+        data.insert(
+            "recipe_id".to_string(),
+            Value::String("abc-123".to_string()),
+        );
+        data.insert("servings".to_string(), Value::from(4));
+
+        let formatted = format_frontend_log_data(&data, true);
+
+        assert_eq!(
+            formatted,
+            r#"{"recipe_id":"<7 chars>","servings":4}"#.to_string()
+        );
+    }
+
+    #[test]
+    fn an_oversized_data_map_is_truncated() {
+        let mut data = BTreeMap::new();
+        for i in 0..MAX_DATA_KEYS + 10 {
+            data.insert(format!("key-{i:02}"), Value::String("x".repeat(100)));
+        }
+
+        let formatted = format_frontend_log_data(&data, false);
+
+        assert!(formatted.ends_with('…'));
+        assert_eq!(formatted.chars().count(), MAX_SERIALIZED_LEN + 1);
+    }
+
+    fn write_log_lines(dir: &std::path::Path, lines: &[&str]) -> std::path::PathBuf {
+        let path = dir.join("feast.log");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn tail_log_entries_of_a_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("feast.log");
+
+        let entries = tail_log_entries(&path, 10, None);
+
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn tail_log_entries_returns_the_most_recent_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_log_lines(
+            dir.path(),
+            &[
+                r#"{"level":"INFO","message":"first"}"#,
+                r#"{"level":"INFO","message":"second"}"#,
+                r#"{"level":"INFO","message":"third"}"#,
+            ],
+        );
+
+        let entries = tail_log_entries(&path, 2, None);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["message"], "second");
+        assert_eq!(entries[1]["message"], "third");
+    }
+
+    #[test]
+    fn tail_log_entries_skips_lines_that_are_not_valid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_log_lines(
+            dir.path(),
+            &[
+                r#"{"level":"INFO","message":"first"}"#,
+                "",
+                "not json at all",
+                r#"{"level":"INFO","message":"second"}"#,
+            ],
+        );
+
+        let entries = tail_log_entries(&path, 10, None);
+
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn tail_log_entries_filters_by_minimum_level() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_log_lines(
+            dir.path(),
+            &[
+                r#"{"level":"ERROR","message":"boom"}"#,
+                r#"{"level":"WARN","message":"careful"}"#,
+                r#"{"level":"INFO","message":"fyi"}"#,
+                r#"{"level":"DEBUG","message":"detail"}"#,
+            ],
+        );
+
+        let entries = tail_log_entries(&path, 10, Some("warn"));
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["message"], "boom");
+        assert_eq!(entries[1]["message"], "careful");
+    }
+
+    #[test]
+    fn tail_log_entries_caps_the_requested_line_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines: Vec<String> = (0..MAX_LOG_TAIL_LINES + 50)
+            .map(|i| format!(r#"{{"level":"INFO","message":"line-{i}"}}"#))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_log_lines(dir.path(), &refs);
+
+        let entries = tail_log_entries(&path, MAX_LOG_TAIL_LINES + 50, None);
+
+        assert_eq!(entries.len(), MAX_LOG_TAIL_LINES);
+    }
+}
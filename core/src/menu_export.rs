@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::MealPlanWithRecipe;
+
+/// Canonical meal-of-the-day ordering for [`render_week_menu_markdown`] and
+/// [`render_week_menu_text`]. A `meal_type` outside this list (a custom
+/// value someone typed in) sorts after all of these, alphabetically among
+/// itself.
+const MEAL_TYPE_ORDER: [&str; 4] = ["breakfast", "lunch", "dinner", "snack"];
+
+fn meal_type_rank(meal_type: &str) -> usize {
+    MEAL_TYPE_ORDER
+        .iter()
+        .position(|&known| known == meal_type)
+        .unwrap_or(MEAL_TYPE_ORDER.len())
+}
+
+/// Buckets `plans` by date (ascending) and orders each day's plans by
+/// [`meal_type_rank`]. A date with no plans simply has no entry — there's
+/// nothing to render "— no meals —" for unless the caller fills in the
+/// gap itself.
+fn group_by_day(plans: &[MealPlanWithRecipe]) -> Vec<(&str, Vec<&MealPlanWithRecipe>)> {
+    let mut by_date: BTreeMap<&str, Vec<&MealPlanWithRecipe>> = BTreeMap::new();
+    for plan in plans {
+        by_date.entry(plan.date.as_str()).or_default().push(plan);
+    }
+
+    let mut days: Vec<(&str, Vec<&MealPlanWithRecipe>)> = by_date.into_iter().collect();
+    for (_, day_plans) in &mut days {
+        day_plans.sort_by(|a, b| {
+            meal_type_rank(&a.meal_type)
+                .cmp(&meal_type_rank(&b.meal_type))
+                .then_with(|| a.meal_type.cmp(&b.meal_type))
+        });
+    }
+    days
+}
+
+/// Renders `plans` as a markdown menu: a `## {date}` heading per day, then
+/// one bulleted `**{meal_type}**: {recipe} (serves {servings})` line per
+/// plan, in [`MEAL_TYPE_ORDER`].
+pub fn render_week_menu_markdown(plans: &[MealPlanWithRecipe]) -> String {
+    let mut menu = String::new();
+    for (date, day_plans) in group_by_day(plans) {
+        menu.push_str(&format!("## {date}\n\n"));
+        for plan in day_plans {
+            menu.push_str(&format!(
+                "- **{}**: {} (serves {})\n",
+                plan.meal_type, plan.recipe_name, plan.servings
+            ));
+        }
+        menu.push('\n');
+    }
+    menu
+}
+
+/// Like [`render_week_menu_markdown`], but as plain indented text with no
+/// markdown markup, for sharing somewhere that won't render it.
+pub fn render_week_menu_text(plans: &[MealPlanWithRecipe]) -> String {
+    let mut menu = String::new();
+    for (date, day_plans) in group_by_day(plans) {
+        menu.push_str(&format!("{date}\n"));
+        for plan in day_plans {
+            menu.push_str(&format!(
+                "  {}: {} (serves {})\n",
+                plan.meal_type, plan.recipe_name, plan.servings
+            ));
+        }
+        menu.push('\n');
+    }
+    menu
+}
+
+/// Fetches `[start_date, end_date]`'s meal plans (via
+/// [`db::meal_plans::get_meal_plans_with_recipes`]) and renders them as a
+/// printable week menu in the requested `format` — `"markdown"` or
+/// `"text"`. Any other value is rejected with a [`AppError::Validation`]
+/// rather than silently falling back to one or the other.
+pub async fn export_week_menu(
+    pool: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    format: &str,
+) -> Result<String, AppError> {
+    let plans = db::meal_plans::get_meal_plans_with_recipes(pool, start_date, end_date).await?;
+
+    match format {
+        "markdown" => Ok(render_week_menu_markdown(&plans)),
+        "text" => Ok(render_week_menu_text(&plans)),
+        other => Err(AppError::Validation(format!(
+            "'{other}' is not a supported menu export format; use \"markdown\" or \"text\""
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(date: &str, meal_type: &str, recipe_name: &str, servings: i64) -> MealPlanWithRecipe {
+        MealPlanWithRecipe {
+            id: "menu-export-id".to_string(),
+            recipe_id: "menu-export-recipe".to_string(),
+            date: date.to_string(),
+            meal_type: meal_type.to_string(),
+            servings,
+            recipe_name: recipe_name.to_string(),
+            recipe_image_path: None,
+        }
+    }
+
+    #[test]
+    fn groups_plans_by_day_in_date_order() {
+        let plans = [
+            plan("2026-08-11", "dinner", "Menu Export Stew", 4),
+            plan("2026-08-10", "breakfast", "Menu Export Pancakes", 2),
+        ];
+
+        let menu = render_week_menu_markdown(&plans);
+
+        let pancakes_index = menu.find("Menu Export Pancakes").unwrap();
+        let stew_index = menu.find("Menu Export Stew").unwrap();
+        assert!(pancakes_index < stew_index);
+        assert!(menu.contains("## 2026-08-10"));
+        assert!(menu.contains("## 2026-08-11"));
+    }
+
+    #[test]
+    fn orders_meals_within_a_day_breakfast_lunch_dinner_snack() {
+        let plans = [
+            plan("2026-08-10", "snack", "Menu Export Chips", 1),
+            plan("2026-08-10", "dinner", "Menu Export Stew", 4),
+            plan("2026-08-10", "breakfast", "Menu Export Pancakes", 2),
+            plan("2026-08-10", "lunch", "Menu Export Soup", 2),
+        ];
+
+        let menu = render_week_menu_markdown(&plans);
+
+        let order: Vec<&str> = [
+            "Menu Export Pancakes",
+            "Menu Export Soup",
+            "Menu Export Stew",
+            "Menu Export Chips",
+        ]
+        .iter()
+        .map(|name| menu.find(name).map(|_| *name).unwrap())
+        .collect();
+        assert_eq!(
+            order,
+            vec![
+                "Menu Export Pancakes",
+                "Menu Export Soup",
+                "Menu Export Stew",
+                "Menu Export Chips",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_format_uses_headings_and_bold_meal_types() {
+        let plans = [plan("2026-08-10", "breakfast", "Menu Export Pancakes", 2)];
+
+        let menu = render_week_menu_markdown(&plans);
+
+        assert_eq!(
+            menu,
+            "## 2026-08-10\n\n- **breakfast**: Menu Export Pancakes (serves 2)\n\n"
+        );
+    }
+
+    #[test]
+    fn text_format_has_no_markdown_markup() {
+        let plans = [plan("2026-08-10", "breakfast", "Menu Export Pancakes", 2)];
+
+        let menu = render_week_menu_text(&plans);
+
+        assert_eq!(
+            menu,
+            "2026-08-10\n  breakfast: Menu Export Pancakes (serves 2)\n\n"
+        );
+        assert!(!menu.contains('#'));
+        assert!(!menu.contains('*'));
+    }
+}
@@ -0,0 +1,480 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Recipe {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i64,
+    pub yield_unit: Option<String>,
+    pub prep_time: Option<i64>,
+    pub cook_time: Option<i64>,
+    pub instructions: String,
+    pub image_path: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub rating_value: Option<f64>,
+    pub rating_count: Option<i64>,
+    pub difficulty: Option<String>,
+    pub yield_notes: Option<String>,
+    /// The user's own 1-5 star rating, set via
+    /// [`crate::db::recipes::set_user_rating`] — independent of
+    /// [`RecipeInput::rating_value`], which comes from an imported source's
+    /// `aggregateRating` rather than the user's opinion.
+    pub user_rating: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecipeInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i64,
+    /// The noun `servings` actually counts (`"cookies"`, `"loaves"`),
+    /// captured from a source's `recipeYield` — `None` means the UI falls
+    /// back to the generic "servings" label.
+    #[serde(default)]
+    pub yield_unit: Option<String>,
+    pub prep_time: Option<i64>,
+    pub cook_time: Option<i64>,
+    pub instructions: Vec<RecipeStep>,
+    pub image_path: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    /// From the source's `aggregateRating`, when imported from one — absent
+    /// for hand-entered recipes.
+    #[serde(default)]
+    pub rating_value: Option<f64>,
+    #[serde(default)]
+    pub rating_count: Option<i64>,
+    /// One of [`DIFFICULTIES`], validated by
+    /// [`crate::db::recipes::validate_difficulty`] — `None` means unrated.
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    /// Free-text caveat on the recipe's yield ("makes 2 loaves, freezes
+    /// well"), distinct from the structured [`RecipeInput::yield_unit`].
+    #[serde(default)]
+    pub yield_notes: Option<String>,
+}
+
+/// The allowed values for [`RecipeInput::difficulty`] — anything else is
+/// rejected by [`crate::db::recipes::validate_difficulty`].
+pub const DIFFICULTIES: [&str; 3] = ["Easy", "Medium", "Hard"];
+
+/// One step of a recipe's instructions. Stored as JSON in the `instructions`
+/// TEXT column, alongside `image` for the per-step photo some sites include
+/// on their `HowToStep`s. [`Deserialize`] also accepts a bare string (no
+/// `image`) so rows written before this field existed still read back fine.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecipeStep {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for RecipeStep {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Step {
+                text: String,
+                #[serde(default)]
+                image: Option<String>,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => RecipeStep { text, image: None },
+            Repr::Step { text, image } => RecipeStep { text, image },
+        })
+    }
+}
+
+impl From<&str> for RecipeStep {
+    fn from(text: &str) -> Self {
+        RecipeStep {
+            text: text.to_string(),
+            image: None,
+        }
+    }
+}
+
+impl From<String> for RecipeStep {
+    fn from(text: String) -> Self {
+        RecipeStep { text, image: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MealPlan {
+    pub id: String,
+    pub recipe_id: String,
+    pub date: String,
+    pub meal_type: String,
+    pub servings: i64,
+    pub created_at: String,
+    pub is_deleted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Ingredient {
+    pub id: String,
+    pub name: String,
+    pub category: Option<String>,
+    pub default_unit: Option<String>,
+    pub created_at: String,
+}
+
+/// A category's ingredients, for the ingredient management screen's
+/// grouped-by-category view rather than a flat alphabetical list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryGroup {
+    pub category: String,
+    pub ingredients: Vec<Ingredient>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ManualShoppingItem {
+    pub id: String,
+    pub week_start: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub is_checked: bool,
+    pub created_at: String,
+    pub deleted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManualShoppingItemInput {
+    pub week_start: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PantryItem {
+    pub id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub restock_threshold: Option<f64>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PantryItemInput {
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub restock_threshold: Option<f64>,
+}
+
+/// A pantry item whose quantity has dropped to (or below) its
+/// `restock_threshold`, with how much more to buy to bring it back up to
+/// that threshold — see [`crate::db::pantry::suggest_restock`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestockSuggestion {
+    pub pantry_item_id: String,
+    pub name: String,
+    pub unit: Option<String>,
+    pub current_quantity: f64,
+    pub restock_threshold: f64,
+    pub suggested_quantity: f64,
+}
+
+/// A staple, summarized across every manual shopping item ever added with
+/// this (case/whitespace-insensitive) name, for "quick add" chips.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FrequentItem {
+    pub name: String,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub use_count: i64,
+}
+
+/// A week's manual shopping list progress, for a UI progress bar ("7 of 12
+/// items checked"). `total` and `checked` only count non-deleted items;
+/// `deleted` is reported separately so the UI can show it was excluded
+/// rather than silently dropped.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ListProgress {
+    pub total: i64,
+    pub checked: i64,
+    pub deleted: i64,
+    pub percent: f64,
+}
+
+/// One week's manual shopping list, summarized for a "move item to
+/// another list" picker — just enough to label and order the choices, not
+/// the items themselves (see [`ManualShoppingItem`] for those). There's no
+/// separate shopping-list entity in the schema; a week's `week_start` is
+/// its id.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ShoppingListSummary {
+    pub week_start: String,
+    pub item_count: i64,
+    pub created_at: String,
+}
+
+/// A named header row for a week's shopping list, returned by
+/// [`crate::db::shopping_lists::get_or_create_week_list`]. `list_type` is
+/// `"default"` for the one auto-created "This Week" list per `week_start`
+/// (enforced by a partial unique index) or `"custom"` for any other list a
+/// user names themselves; it doesn't govern which [`ManualShoppingItem`]s
+/// belong to the week — those are still just grouped by `week_start`.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ShoppingList {
+    pub id: String,
+    pub week_start: String,
+    pub name: String,
+    pub list_type: String,
+    pub created_at: String,
+}
+
+/// A manual shopping item present on both lists compared by
+/// [`crate::db::manual_items::diff_lists`], but with a different quantity on
+/// each. `unit` is list A's unit — `quantity_b` has already been converted
+/// into it wherever the two lists used compatible but different units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShoppingListQuantityDiff {
+    pub name: String,
+    pub quantity_a: f64,
+    pub quantity_b: f64,
+    pub unit: String,
+}
+
+/// The result of comparing two weeks' manual shopping lists by
+/// [`crate::db::manual_items::diff_lists`], matching items on normalized
+/// name and a compatible unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDiff {
+    pub only_in_a: Vec<ManualShoppingItem>,
+    pub only_in_b: Vec<ManualShoppingItem>,
+    pub differing: Vec<ShoppingListQuantityDiff>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ManualShoppingItemUpdate {
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub is_checked: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DayTimeTotal {
+    pub date: String,
+    pub total_minutes: i64,
+}
+
+/// How much of an [`AggregatedShoppingItem`]'s total a single recipe
+/// contributed, in that recipe's own unit (i.e. before unit unification).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShoppingItemContribution {
+    pub recipe_id: String,
+    pub quantity: f64,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedShoppingItem {
+    pub name: String,
+    pub category: Option<String>,
+    pub quantity: f64,
+    pub unit: String,
+    pub source_recipe_ids: Vec<String>,
+    pub contributions: Vec<ShoppingItemContribution>,
+}
+
+/// An ingredient used by more than one planned recipe in a date range, for
+/// batch-prep planning — see
+/// [`crate::db::shopping_list::get_shared_ingredients`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedIngredient {
+    pub name: String,
+    pub recipe_count: i64,
+    pub recipe_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct QuickList {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    /// Set by [`crate::db::quick_lists::add_quick_list_to_shopping`] every
+    /// time this list is copied into a week's shopping list — `None` if
+    /// it's never been used, for surfacing stale templates.
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuickListInput {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct QuickListItem {
+    pub id: String,
+    pub quick_list_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuickListItemInput {
+    pub quick_list_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickListWithItems {
+    pub list: QuickList,
+    pub items: Vec<QuickListItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecipeQualityIssue {
+    pub recipe_id: String,
+    pub name: String,
+    pub missing_instructions: bool,
+    pub missing_ingredients: bool,
+    pub missing_image: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecipeIngredientExport {
+    pub name: String,
+    pub quantity: f64,
+    pub unit: String,
+    pub notes: Option<String>,
+    pub sort_order: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeExport {
+    pub name: String,
+    pub description: Option<String>,
+    pub servings: i64,
+    #[serde(default)]
+    pub yield_unit: Option<String>,
+    pub prep_time: Option<i64>,
+    pub cook_time: Option<i64>,
+    pub instructions: Vec<RecipeStep>,
+    pub image_path: Option<String>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub rating_value: Option<f64>,
+    #[serde(default)]
+    pub rating_count: Option<i64>,
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    #[serde(default)]
+    pub yield_notes: Option<String>,
+    pub ingredients: Vec<RecipeIngredientExport>,
+    pub tags: Vec<String>,
+}
+
+/// The root backup document. `version` lets `import_all_recipes` reject
+/// bundles from an incompatible future format instead of silently
+/// misreading them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeBackup {
+    pub version: u32,
+    pub recipes: Vec<RecipeExport>,
+}
+
+/// A [`MealPlan`] with its recipe's name and image inlined, for a week view
+/// that would otherwise need a per-recipe detail fetch for every planned
+/// meal just to render a label and thumbnail.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MealPlanWithRecipe {
+    pub id: String,
+    pub recipe_id: String,
+    pub date: String,
+    pub meal_type: String,
+    pub servings: i64,
+    pub recipe_name: String,
+    pub recipe_image_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MealPlanInput {
+    pub recipe_id: String,
+    pub date: String,
+    pub meal_type: String,
+    /// Defaults to the planned recipe's own `servings` when omitted.
+    #[serde(default)]
+    pub servings: Option<i64>,
+}
+
+/// One slot of a repeating "template week" (e.g. "Taco Tuesday, Pasta
+/// Friday") applied to a chosen week via
+/// [`crate::db::meal_plans::apply_template`]. `day_offset` is 0-6, added to
+/// the target week's start date to get the slot's actual date.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MealPlanTemplateEntry {
+    pub day_offset: i64,
+    pub meal_type: String,
+    pub recipe_id: String,
+    /// Defaults to the planned recipe's own `servings` when omitted.
+    #[serde(default)]
+    pub servings: Option<i64>,
+}
+
+/// Everything a week view screen needs to render in one call: planned meals
+/// (with their recipe's name/image inlined), the week's recipe-derived
+/// shopping list, and its manual items — see
+/// [`crate::week_view::get_week_view`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeekView {
+    pub meal_plans: Vec<MealPlanWithRecipe>,
+    pub shopping_list: Vec<AggregatedShoppingItem>,
+    pub manual_items: Vec<ManualShoppingItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recipe_step_deserializes_the_legacy_plain_string_shape() {
+        let steps: Vec<RecipeStep> = serde_json::from_str(r#"["Mix", "Bake"]"#).unwrap();
+        assert_eq!(
+            steps,
+            vec![RecipeStep::from("Mix"), RecipeStep::from("Bake")]
+        );
+    }
+
+    #[test]
+    fn recipe_step_deserializes_the_structured_shape_with_an_image() {
+        let steps: Vec<RecipeStep> =
+            serde_json::from_str(r#"[{"text": "Mix", "image": "https://example.com/mix.jpg"}]"#)
+                .unwrap();
+        assert_eq!(
+            steps,
+            vec![RecipeStep {
+                text: "Mix".to_string(),
+                image: Some("https://example.com/mix.jpg".to_string()),
+            }]
+        );
+    }
+}
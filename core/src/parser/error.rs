@@ -0,0 +1,18 @@
+/// Errors from extracting a recipe out of a fetched page.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("no JSON-LD found on the page")]
+    NoJsonLd,
+
+    #[error("JSON-LD was present but described no Recipe")]
+    NotARecipe,
+
+    #[error("JSON-LD described more than one Recipe")]
+    MultipleRecipes,
+
+    #[error("recipe JSON-LD was malformed: {0}")]
+    MalformedJson(String),
+
+    #[error("page was too large to parse safely")]
+    TooLarge,
+}
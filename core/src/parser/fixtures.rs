@@ -0,0 +1,109 @@
+//! Table-driven regression harness: every `<site>.html` fixture in this
+//! directory is paired with a `<site>.expected.json` describing either the
+//! `ParsedRecipe` we expect `parse_recipe_html` to produce, or the specific
+//! `ParseError` it should fail with. Adding coverage for a newly-broken
+//! site is just dropping two files here — no new test function needed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::parser::error::ParseError;
+use crate::parser::recipe::{parse_recipe_html, ParseWarning, ParsedRecipe};
+
+#[derive(Debug, Deserialize)]
+struct ExpectedSuccess {
+    name: String,
+    ingredient_count: usize,
+    instruction_count: usize,
+    servings: Option<i64>,
+    #[serde(default)]
+    yield_unit: Option<String>,
+    has_image: bool,
+    #[serde(default)]
+    rating_value: Option<f64>,
+    #[serde(default)]
+    rating_count: Option<i64>,
+    #[serde(default)]
+    warning: Option<ParseWarning>,
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parser/fixtures")
+}
+
+#[test]
+fn fixtures_parse_as_expected() {
+    let dir = fixtures_dir();
+    let mut html_fixtures: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("fixtures directory should exist")
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .collect();
+    html_fixtures.sort();
+    assert!(!html_fixtures.is_empty(), "expected at least one fixture");
+
+    for html_path in html_fixtures {
+        let expected_path = html_path.with_extension("expected.json");
+        let fixture_name = html_path.file_stem().unwrap().to_string_lossy().to_string();
+
+        let html = fs::read_to_string(&html_path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", html_path.display()));
+        let expected_raw = fs::read_to_string(&expected_path).unwrap_or_else(|e| {
+            panic!(
+                "missing expectation file {} for fixture '{fixture_name}': {e}",
+                expected_path.display()
+            )
+        });
+        let expected: Value = serde_json::from_str(&expected_raw)
+            .unwrap_or_else(|e| panic!("malformed expectation JSON for '{fixture_name}': {e}"));
+
+        let actual = parse_recipe_html(&html);
+
+        if let Some(error_name) = expected.get("error").and_then(Value::as_str) {
+            let expected_error = parse_error_by_name(error_name).unwrap_or_else(|| {
+                panic!("unknown expected error '{error_name}' in fixture '{fixture_name}'")
+            });
+            assert_eq!(
+                actual,
+                Err(expected_error),
+                "fixture '{fixture_name}' did not fail the way expected"
+            );
+        } else {
+            let expected: ExpectedSuccess = serde_json::from_value(expected).unwrap_or_else(|e| {
+                panic!("expectation for '{fixture_name}' doesn't match success shape: {e}")
+            });
+            let parsed =
+                actual.unwrap_or_else(|e| panic!("fixture '{fixture_name}' failed to parse: {e}"));
+            assert_eq!(
+                parsed,
+                ParsedRecipe {
+                    name: expected.name,
+                    ingredient_count: expected.ingredient_count,
+                    instruction_count: expected.instruction_count,
+                    servings: expected.servings,
+                    yield_unit: expected.yield_unit,
+                    has_image: expected.has_image,
+                    rating_value: expected.rating_value,
+                    rating_count: expected.rating_count,
+                    warning: expected.warning,
+                },
+                "fixture '{fixture_name}' parsed unexpectedly"
+            );
+        }
+    }
+}
+
+fn parse_error_by_name(name: &str) -> Option<ParseError> {
+    match name {
+        "NoJsonLd" => Some(ParseError::NoJsonLd),
+        "NotARecipe" => Some(ParseError::NotARecipe),
+        other if other.starts_with("MalformedJson") => {
+            Some(ParseError::MalformedJson(String::new()))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,51 @@
+use serde_json::Value;
+
+/// Returns whether `value`'s `@type` field identifies it as a schema.org
+/// `Recipe` object. Handles both a bare string and an array of strings
+/// (some sites list multiple types, e.g. `["Recipe", "Article"]`), and is
+/// tolerant of surrounding whitespace and casing since a few hand-authored
+/// pages write `"recipe"` or `" Recipe "`.
+pub fn is_recipe_type(value: &Value) -> bool {
+    match value.get("@type") {
+        Some(Value::String(s)) => is_recipe_string(s),
+        Some(Value::Array(items)) => items
+            .iter()
+            .any(|item| matches!(item, Value::String(s) if is_recipe_string(s))),
+        _ => false,
+    }
+}
+
+fn is_recipe_string(s: &str) -> bool {
+    s.trim().eq_ignore_ascii_case("recipe")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn recognizes_exact_type() {
+        assert!(is_recipe_type(&json!({"@type": "Recipe"})));
+    }
+
+    #[test]
+    fn recognizes_lowercase_type() {
+        assert!(is_recipe_type(&json!({"@type": "recipe"})));
+    }
+
+    #[test]
+    fn recognizes_whitespace_padded_type() {
+        assert!(is_recipe_type(&json!({"@type": " Recipe "})));
+    }
+
+    #[test]
+    fn recognizes_array_of_types() {
+        assert!(is_recipe_type(&json!({"@type": ["Article", "Recipe"]})));
+    }
+
+    #[test]
+    fn rejects_unrelated_type() {
+        assert!(!is_recipe_type(&json!({"@type": "Article"})));
+    }
+}
@@ -0,0 +1,80 @@
+use scraper::{Html, Selector};
+use url::Url;
+
+/// Extracts a `<link rel="canonical">` URL from `html`, resolved against
+/// `base` if relative. Falls back to `<link rel="amphtml">` when there's no
+/// canonical link — category and AMP pages often point at the real recipe
+/// page through one or the other. Returns `None` if neither is present, the
+/// link has no `href`, or `base`/`href` can't be resolved into a URL.
+pub fn extract_canonical_url(html: &str, base: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("link[rel]").expect("static link[rel] selector is valid");
+
+    let mut amphtml = None;
+    for link in document.select(&selector) {
+        let Some(href) = link.value().attr("href") else {
+            continue;
+        };
+        match link.value().attr("rel") {
+            Some("canonical") => return resolve(base, href),
+            Some("amphtml") if amphtml.is_none() => amphtml = resolve(base, href),
+            _ => {}
+        }
+    }
+    amphtml
+}
+
+fn resolve(base: &str, href: &str) -> Option<String> {
+    let base = Url::parse(base).ok()?;
+    base.join(href).ok().map(|url| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_an_absolute_canonical_link() {
+        let html = r#"<html><head><link rel="canonical" href="https://example.com/real-recipe"></head></html>"#;
+        assert_eq!(
+            extract_canonical_url(html, "https://example.com/category/x"),
+            Some("https://example.com/real-recipe".to_string())
+        );
+    }
+
+    #[test]
+    fn resolves_a_relative_canonical_link_against_the_base() {
+        let html = r#"<html><head><link rel="canonical" href="/real-recipe"></head></html>"#;
+        assert_eq!(
+            extract_canonical_url(html, "https://example.com/category/x"),
+            Some("https://example.com/real-recipe".to_string())
+        );
+    }
+
+    #[test]
+    fn falls_back_to_amphtml_when_there_is_no_canonical() {
+        let html = r#"<html><head><link rel="amphtml" href="/amp/real-recipe"></head></html>"#;
+        assert_eq!(
+            extract_canonical_url(html, "https://example.com/category/x"),
+            Some("https://example.com/amp/real-recipe".to_string())
+        );
+    }
+
+    #[test]
+    fn prefers_canonical_over_amphtml() {
+        let html = r#"<html><head>
+            <link rel="amphtml" href="/amp/real-recipe">
+            <link rel="canonical" href="/real-recipe">
+        </head></html>"#;
+        assert_eq!(
+            extract_canonical_url(html, "https://example.com/category/x"),
+            Some("https://example.com/real-recipe".to_string())
+        );
+    }
+
+    #[test]
+    fn returns_none_when_neither_link_is_present() {
+        let html = "<html><head></head></html>";
+        assert_eq!(extract_canonical_url(html, "https://example.com/x"), None);
+    }
+}
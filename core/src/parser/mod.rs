@@ -0,0 +1,7 @@
+pub mod error;
+pub mod jsonld;
+pub mod links;
+pub mod recipe;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1,1115 @@
+use scraper::{Html, Selector};
+use serde::Serialize;
+use serde_json::{Deserializer, Value};
+
+use crate::error::AppError;
+use crate::models::RecipeStep;
+use crate::parser::error::ParseError;
+use crate::parser::jsonld::is_recipe_type;
+use crate::utils::units::{normalize_unicode_fractions, parse_measurement, parse_number_token};
+
+/// The handful of fields the importer's regression harness cares about.
+/// Not a full schema.org Recipe mapping (see [`crate::models::RecipeInput`]
+/// for that) — just enough to assert a fixture parsed the way we expect.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedRecipe {
+    pub name: String,
+    pub ingredient_count: usize,
+    pub instruction_count: usize,
+    pub servings: Option<i64>,
+    pub yield_unit: Option<String>,
+    pub has_image: bool,
+    pub rating_value: Option<f64>,
+    pub rating_count: Option<i64>,
+    pub warning: Option<ParseWarning>,
+}
+
+/// A non-fatal quality signal from [`parse_recipe_json`] — unlike
+/// [`ParseError`], this doesn't stop the parse; it's surfaced alongside a
+/// successful [`ParsedRecipe`] so a preview UI can flag a likely-bad
+/// extraction before the user commits to importing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
+pub enum ParseWarning {
+    /// Most of the parsed `recipeIngredient` lines have no quantity and a
+    /// suspiciously short, single-word name — the shape of nav-menu links
+    /// or other boilerplate text mistaken for a real ingredient list.
+    LowConfidenceIngredients,
+}
+
+/// How many of the signals that usually separate a clean recipe import
+/// from a bad one are present in `parsed`, as a score from `0.0` to `1.0`
+/// for a preview UI to bucket via [`ImportConfidence::from_score`].
+/// Checks the name being non-empty, more than one ingredient, more than
+/// one instruction, a servings figure having parsed, and an image having
+/// been found. [`ParsedRecipe`] doesn't track per-ingredient units or
+/// prep/cook times, so those can't be scored here; a [`ParseWarning`]
+/// halves whatever score the signals alone would give, since it already
+/// means the extraction looked suspicious. The core parse itself
+/// ([`parse_recipe_html`], [`parse_recipe_json`]) is unaffected — this is
+/// purely a read of the result they already produced.
+pub fn parse_confidence(parsed: &ParsedRecipe) -> f64 {
+    let signals = [
+        !parsed.name.trim().is_empty(),
+        parsed.ingredient_count > 1,
+        parsed.instruction_count > 1,
+        parsed.servings.is_some(),
+        parsed.has_image,
+    ];
+    let matched = signals.iter().filter(|signal| **signal).count() as f64;
+    let score = matched / signals.len() as f64;
+    if parsed.warning.is_some() {
+        score * 0.5
+    } else {
+        score
+    }
+}
+
+/// A bucketed reading of [`parse_confidence`] for a preview UI to show as
+/// "High/Medium/Low confidence import" without exposing the raw score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportConfidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl ImportConfidence {
+    /// Thresholds chosen so a fully-parsed recipe (all five signals) reads
+    /// `High`, and dropping just one of the five (4/5 = 0.8) still does —
+    /// it takes two missing signals (3/5 = 0.6) to fall to `Medium`, and
+    /// more than that — or carrying a [`ParseWarning`]'s score penalty —
+    /// to read `Low`.
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.8 {
+            ImportConfidence::High
+        } else if score >= 0.5 {
+            ImportConfidence::Medium
+        } else {
+            ImportConfidence::Low
+        }
+    }
+}
+
+/// Fraction of ingredient lines above which [`looks_like_garbage_ingredients`]
+/// flags the parse — high enough that a handful of genuinely bare
+/// ingredients ("Salt", "Pepper") in an otherwise normal list don't trip it.
+const LOW_CONFIDENCE_THRESHOLD: f64 = 0.6;
+
+/// True when `lines` are dominated by entries that [`parse_measurement`]
+/// reads as having no quantity and a single short word left over — real
+/// ingredient lines almost always have either a quantity ("2 cups flour")
+/// or more than a word or two of descriptive text ("salt, to taste"), so a
+/// list mostly missing both looks more like scraped nav links than food.
+/// Lists under three entries are left alone; a short manually-entered list
+/// of single-word ingredients is too easily legitimate to flag.
+fn looks_like_garbage_ingredients(lines: &[&str]) -> bool {
+    if lines.len() < 3 {
+        return false;
+    }
+    let suspicious = lines
+        .iter()
+        .filter(|line| {
+            let (quantity, name, _package_size) = parse_measurement(line);
+            quantity == 0.0 && name.split_whitespace().count() <= 1 && name.len() <= 12
+        })
+        .count();
+    (suspicious as f64 / lines.len() as f64) >= LOW_CONFIDENCE_THRESHOLD
+}
+
+/// Byte length above which [`parse_recipe_html`] rejects a page before
+/// `Html::parse_document` ever sees it. Well under
+/// [`crate::importer::fetch::MAX_RESPONSE_SIZE`] — a real recipe page has
+/// no business being anywhere near this large, and `scraper`'s HTML
+/// parsing is worse than linear on deeply nested or degenerate markup, so
+/// a maliciously huge document that slipped under the fetch cap can still
+/// burn significant CPU/memory if it's allowed to reach the parser.
+pub const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Extracts the first schema.org `Recipe` described in `html`'s JSON-LD
+/// `<script>` tags.
+pub fn parse_recipe_html(html: &str) -> Result<ParsedRecipe, ParseError> {
+    if html.len() > MAX_PARSE_BYTES {
+        return Err(ParseError::TooLarge);
+    }
+
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#)
+        .expect("static JSON-LD selector is valid");
+
+    let mut scripts_seen = 0;
+    for script in document.select(&selector) {
+        scripts_seen += 1;
+        let text = script.text().collect::<String>();
+        for value in extract_jsonld_blocks(&text) {
+            match parse_recipe_json(&value) {
+                Ok(parsed) => return Ok(parsed),
+                Err(ParseError::MultipleRecipes) => return Err(ParseError::MultipleRecipes),
+                Err(_) => continue,
+            }
+        }
+    }
+
+    // Last resort for the handful of sites that stash JSON-LD HTML-escaped
+    // inside a `<template>` rather than a `<script>` tag — `scraper` already
+    // decodes the entities for us, so this is really just widening where we
+    // look, not adding any decoding of our own. Only reached once the
+    // script-tag path above has already come up empty, so ordinary pages
+    // never pay for it.
+    let template_selector = Selector::parse("template").expect("static template selector is valid");
+    for template in document.select(&template_selector) {
+        for value in extract_template_fallback_blocks(&template.text().collect::<String>()) {
+            match parse_recipe_json(&value) {
+                Ok(parsed) => return Ok(parsed),
+                Err(ParseError::MultipleRecipes) => return Err(ParseError::MultipleRecipes),
+                Err(_) => continue,
+            }
+        }
+    }
+
+    if scripts_seen == 0 {
+        Err(ParseError::NoJsonLd)
+    } else {
+        Err(ParseError::NotARecipe)
+    }
+}
+
+/// Pulls every JSON-LD object out of a single `<script>` tag's text, tolerant
+/// of the handful of shapes sites actually put there: a single object, a
+/// top-level array of objects, or several objects simply concatenated or
+/// newline-separated with no enclosing array at all. The latter isn't valid
+/// JSON as a whole, so it's read with a streaming [`Deserializer`] that
+/// recovers each well-formed value in turn instead of failing the entire
+/// script on the first parse error. A top-level array is kept as a single
+/// block rather than flattened into its items — [`find_recipe_object`]
+/// already knows how to pick a lone `Recipe` out of an array, and needs to
+/// see the whole array at once to flag a roundup page's several `Recipe`
+/// items as [`ParseError::MultipleRecipes`] instead of silently matching the
+/// first one.
+fn extract_jsonld_blocks(text: &str) -> Vec<Value> {
+    Deserializer::from_str(text).into_iter::<Value>().flatten().collect()
+}
+
+/// Recovers JSON-LD from a `<template>`'s already-decoded contents. Some
+/// sites escape an entire `<script type="application/ld+json">` tag as text
+/// inside the template, in which case re-parsing that text as an HTML
+/// fragment turns it back into a real `<script>` element we can pull the
+/// JSON out of the normal way. Others skip the wrapper `<script>` entirely
+/// and just escape the bare JSON, so if the fragment re-parse finds nothing,
+/// this falls back to reading the template's text directly via
+/// [`extract_jsonld_blocks`].
+fn extract_template_fallback_blocks(template_html: &str) -> Vec<Value> {
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#)
+        .expect("static JSON-LD selector is valid");
+    let fragment = Html::parse_fragment(template_html);
+    let blocks: Vec<Value> = fragment
+        .select(&selector)
+        .flat_map(|script| extract_jsonld_blocks(&script.text().collect::<String>()))
+        .collect();
+
+    if blocks.is_empty() {
+        extract_jsonld_blocks(template_html)
+    } else {
+        blocks
+    }
+}
+
+/// Like [`parse_recipe_html`], but runs it on a blocking thread via
+/// [`tokio::task::spawn_blocking`] rather than the calling task. `scraper`'s
+/// HTML parsing is synchronous and CPU-bound, so running it directly on an
+/// async task would stall the runtime's executor thread for however long a
+/// large or pathological document takes to parse.
+pub async fn parse_recipe_html_blocking(html: String) -> Result<ParsedRecipe, AppError> {
+    tokio::task::spawn_blocking(move || parse_recipe_html(&html))
+        .await
+        .map_err(|e| AppError::Internal(format!("recipe parsing task failed: {e}")))?
+        .map_err(AppError::from)
+}
+
+/// Like [`parse_recipe_html`], but for a JSON-LD document that's already
+/// been deserialized (or pasted in directly, rather than scraped out of a
+/// page's `<script>` tags) — e.g. a user copying the JSON-LD object itself
+/// out of a site's source.
+pub fn parse_recipe_json(value: &Value) -> Result<ParsedRecipe, ParseError> {
+    let recipe = find_recipe_object(value)?.ok_or(ParseError::NotARecipe)?;
+    Ok(to_parsed_recipe(recipe))
+}
+
+/// Walks a JSON-LD document looking for a `Recipe` node, following the two
+/// shapes sites commonly use: a bare array of nodes, and a `@graph` array.
+/// Returns [`ParseError::MultipleRecipes`] rather than picking one when a
+/// document describes more than one `Recipe` (a roundup page, say) — there's
+/// no reliable way to tell which one the caller actually wanted.
+pub(crate) fn find_recipe_object(value: &Value) -> Result<Option<&Value>, ParseError> {
+    if is_recipe_type(value) {
+        return Ok(Some(value));
+    }
+
+    let candidates: Vec<&Value> = if let Some(items) = value.as_array() {
+        items.iter().filter(|item| is_recipe_type(item)).collect()
+    } else if let Some(graph) = value.get("@graph").and_then(Value::as_array) {
+        graph.iter().filter(|item| is_recipe_type(item)).collect()
+    } else {
+        Vec::new()
+    };
+
+    match candidates.len() {
+        0 => Ok(None),
+        1 => Ok(Some(candidates[0])),
+        _ => Err(ParseError::MultipleRecipes),
+    }
+}
+
+fn to_parsed_recipe(recipe: &Value) -> ParsedRecipe {
+    let name = sanitize_text(
+        recipe
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("Untitled Recipe"),
+    );
+
+    let ingredient_count = recipe
+        .get("recipeIngredient")
+        .and_then(Value::as_array)
+        .map_or(0, |items| items.len());
+
+    let instruction_count = match recipe.get("recipeInstructions") {
+        Some(Value::Array(items)) => items.len(),
+        Some(Value::String(text)) => usize::from(!text.trim().is_empty()),
+        _ => 0,
+    };
+
+    let (servings, yield_unit) = recipe
+        .get("recipeYield")
+        .map(parse_servings)
+        .unwrap_or((None, None));
+
+    let has_image = recipe.get("image").is_some_and(|image| !image.is_null());
+
+    let aggregate_rating = recipe.get("aggregateRating");
+    let rating_value = aggregate_rating
+        .and_then(|r| r.get("ratingValue"))
+        .and_then(extract_f64);
+    let rating_count = aggregate_rating
+        .and_then(|r| r.get("reviewCount").or_else(|| r.get("ratingCount")))
+        .and_then(extract_first_integer);
+
+    let ingredient_lines: Vec<&str> = recipe
+        .get("recipeIngredient")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let warning = looks_like_garbage_ingredients(&ingredient_lines)
+        .then_some(ParseWarning::LowConfidenceIngredients);
+
+    ParsedRecipe {
+        name,
+        ingredient_count,
+        instruction_count,
+        servings,
+        yield_unit,
+        has_image,
+        rating_value,
+        rating_count,
+        warning,
+    }
+}
+
+/// Extracts the full step-by-step text (and per-step image, when present)
+/// out of `recipe`'s `recipeInstructions`, for callers that want the actual
+/// content rather than [`to_parsed_recipe`]'s bare count. Handles a single
+/// bare string, an array of bare strings, an array of `HowToStep` objects
+/// (`{"text": ..., "image": ...}`), and `HowToSection` objects (grouped
+/// steps under a `name` like `"For the sauce:"`) — see
+/// [`parse_instructions_with_headers`] for how section names are handled.
+/// Unrecognized array items are skipped rather than failing the whole
+/// recipe.
+pub fn parse_instructions(recipe: &Value) -> Vec<RecipeStep> {
+    parse_instructions_with_headers(recipe, true)
+}
+
+/// Like [`parse_instructions`], but lets the caller opt out of the header
+/// steps a `HowToSection`'s `name` would otherwise contribute. When
+/// `include_headers` is true (the default via [`parse_instructions`]),
+/// each section's `name` is inserted as its own step immediately before
+/// that section's steps, so the grouping ("For the sauce:", "For the
+/// crust:") survives flattening instead of being discarded.
+pub fn parse_instructions_with_headers(recipe: &Value, include_headers: bool) -> Vec<RecipeStep> {
+    match recipe.get("recipeInstructions") {
+        Some(Value::String(text)) if !text.trim().is_empty() => {
+            vec![RecipeStep::from(sanitize_text(text))]
+        }
+        Some(Value::Array(items)) => items
+            .iter()
+            .flat_map(|item| flatten_instruction_item(item, include_headers))
+            .collect(),
+        Some(item @ Value::Object(_)) => flatten_instruction_item(item, include_headers),
+        _ => Vec::new(),
+    }
+}
+
+/// `HowToSection`s most often list their steps under `itemListElement`, but
+/// some generators use the plain `step` instead — both are treated the
+/// same way.
+fn flatten_instruction_item(item: &Value, include_headers: bool) -> Vec<RecipeStep> {
+    let Some(section_steps) = item
+        .get("itemListElement")
+        .or_else(|| item.get("step"))
+        .and_then(Value::as_array)
+    else {
+        return parse_instruction_item(item).into_iter().collect();
+    };
+
+    let mut steps = Vec::new();
+    if include_headers {
+        if let Some(name) = item.get("name").and_then(Value::as_str) {
+            if !name.trim().is_empty() {
+                steps.push(RecipeStep::from(sanitize_text(name)));
+            }
+        }
+    }
+    steps.extend(section_steps.iter().filter_map(parse_instruction_item));
+    steps
+}
+
+fn parse_instruction_item(item: &Value) -> Option<RecipeStep> {
+    match item {
+        Value::String(text) if !text.trim().is_empty() => {
+            Some(RecipeStep::from(sanitize_text(text)))
+        }
+        Value::Object(_) => {
+            let text = item.get("text").and_then(Value::as_str)?;
+            let image = item.get("image").and_then(extract_image_url);
+            Some(RecipeStep {
+                text: sanitize_text(text),
+                image,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A `HowToStep`'s `image` is commonly a bare URL string, an `ImageObject`
+/// with a `url` field, or an array of either (use the first).
+pub(crate) fn extract_image_url(value: &Value) -> Option<String> {
+    match value {
+        Value::String(url) => Some(url.clone()),
+        Value::Array(items) => items.first().and_then(extract_image_url),
+        Value::Object(_) => value.get("url").and_then(Value::as_str).map(String::from),
+        _ => None,
+    }
+}
+
+/// Pulls every tag-like value off a JSON-LD recipe's `recipeCategory`,
+/// `recipeCuisine`, and `keywords` fields into one normalized, deduplicated
+/// list, for tagging an imported recipe with more than the single value
+/// earlier versions kept. Each field may be a bare string (comma-separated
+/// values included, as sites commonly pack `keywords` that way), or an
+/// array of strings — both shapes flatten into the same list. Comparison
+/// for deduplication is case-insensitive, but the first-seen casing is
+/// what's kept.
+pub(crate) fn extract_tags(recipe: &Value) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for field in ["recipeCategory", "recipeCuisine", "keywords"] {
+        for raw in tag_field_values(recipe.get(field)) {
+            let tag = sanitize_text(raw.trim());
+            if tag.is_empty() {
+                continue;
+            }
+            if seen.insert(tag.to_lowercase()) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    tags
+}
+
+fn tag_field_values(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .flat_map(|s| s.split(',').map(str::to_string))
+            .collect(),
+        Some(Value::String(s)) => s.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `recipeYield` is commonly a bare number, a numeric string, or a string
+/// like `"4 servings"`; pulls the leading integer out of any of those.
+pub(crate) fn extract_first_integer(value: &Value) -> Option<i64> {
+    let text = match value {
+        Value::Number(n) => return n.as_i64(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.first().and_then(Value::as_str)?.to_string(),
+        _ => return None,
+    };
+    let digits: String = text.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Like [`extract_first_integer`], but for `recipeYield` specifically:
+/// understands Unicode vulgar fractions (via
+/// [`normalize_unicode_fractions`]) and mixed numbers (`"1½ dozen"`),
+/// converts a `"dozen"` right after the quantity into its count of 12 (so
+/// "1½ dozen" reduces to 18 rather than just the leading `1`), and returns
+/// whatever noun trails the quantity (`"24 cookies"` -> `"cookies"`) as a
+/// `yield_unit` the UI can show instead of a generic "servings", falling
+/// back to `None` when the yield is just a bare number.
+pub(crate) fn parse_servings(value: &Value) -> (Option<i64>, Option<String>) {
+    let text = match value {
+        Value::Number(n) => return (n.as_i64(), None),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => match items.first().and_then(Value::as_str) {
+            Some(s) => s.to_string(),
+            None => return (None, None),
+        },
+        _ => return (None, None),
+    };
+    let text = normalize_unicode_fractions(&text);
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+
+    let Some(mut quantity) = tokens.first().and_then(|t| parse_number_token(t)) else {
+        return (None, None);
+    };
+    let mut consumed = 1;
+    if let Some(second) = tokens.get(1) {
+        if second.contains('/') {
+            if let Some(fraction) = parse_number_token(second) {
+                quantity += fraction;
+                consumed = 2;
+            }
+        }
+    }
+
+    if tokens
+        .get(consumed)
+        .is_some_and(|t| t.eq_ignore_ascii_case("dozen"))
+    {
+        quantity *= 12.0;
+        consumed += 1;
+    }
+
+    let yield_unit = tokens[consumed..].join(" ");
+    let yield_unit = (!yield_unit.is_empty()).then_some(yield_unit);
+
+    (Some(quantity.round() as i64), yield_unit)
+}
+
+/// `ratingValue` is commonly a bare number, but some sites render it as a
+/// numeric string (e.g. `"4.7"`) instead.
+pub(crate) fn extract_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Undoes a common double-encoding mistake, where a page HTML-escapes its
+/// text *before* dropping it into JSON-LD, so a named entity like `&#39;`
+/// ends up escaped a second time into `&amp;#39;`. Only the numeric form is
+/// handled, since that's what the double-escaping actually produces; a
+/// literal `&amp;#` that isn't followed by `digits;` is left untouched
+/// rather than guessed at.
+fn repair_double_encoded_numeric_entities(text: &str) -> String {
+    const MARKER: &str = "&amp;#";
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(MARKER) {
+        result.push_str(&rest[..start]);
+        let after_marker = &rest[start + MARKER.len()..];
+        let digits_end = after_marker
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after_marker.len());
+        let digits = &after_marker[..digits_end];
+        let decoded = (!digits.is_empty() && after_marker[digits_end..].starts_with(';'))
+            .then(|| digits.parse::<u32>().ok())
+            .flatten()
+            .and_then(char::from_u32);
+        match decoded {
+            Some(ch) => {
+                result.push(ch);
+                rest = &after_marker[digits_end + 1..];
+            }
+            None => {
+                result.push_str(MARKER);
+                rest = after_marker;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Undoes the classic "UTF-8 misread as Latin-1" mojibake, where a byte
+/// sequence like `é`'s UTF-8 encoding gets re-decoded one byte at a time
+/// into `Ã©`. Bails out immediately unless the telltale `Ã`/`Â` lead bytes
+/// are present, and only commits to the repair if treating every character
+/// as a raw Latin-1 byte actually yields valid UTF-8 — anything else is
+/// left alone rather than risk mangling legitimate text.
+fn repair_common_mojibake(text: &str) -> String {
+    if !text.contains('Ã') && !text.contains('Â') {
+        return text.to_string();
+    }
+    let bytes: Option<Vec<u8>> = text.chars().map(|c| u8::try_from(c as u32).ok()).collect();
+    match bytes.and_then(|b| String::from_utf8(b).ok()) {
+        Some(repaired) => repaired,
+        None => text.to_string(),
+    }
+}
+
+/// Strips control characters and null bytes out of scraped text, after
+/// whatever entity decoding already happened upstream, and repairs the
+/// double-encoding and mojibake artifacts that decoding sometimes leaves
+/// behind. Bad encoding on some sites' JSON-LD leaks these into otherwise-
+/// plain names, descriptions and instruction steps, where they can break
+/// display or trip up string handling further down the pipeline once
+/// stored. Newlines and tabs are kept, since those are legitimate
+/// formatting rather than mangled bytes.
+pub(crate) fn sanitize_text(text: impl AsRef<str>) -> String {
+    let text = repair_common_mojibake(text.as_ref());
+    let text = repair_double_encoded_numeric_entities(&text);
+    text.chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_a_full_aggregate_rating_block() {
+        let recipe = json!({
+            "name": "Rated Recipe",
+            "aggregateRating": {
+                "@type": "AggregateRating",
+                "ratingValue": "4.7",
+                "reviewCount": "128",
+            }
+        });
+
+        let parsed = to_parsed_recipe(&recipe);
+
+        assert_eq!(parsed.rating_value, Some(4.7));
+        assert_eq!(parsed.rating_count, Some(128));
+    }
+
+    #[test]
+    fn a_missing_aggregate_rating_leaves_the_fields_none() {
+        let recipe = json!({ "name": "Unrated Recipe" });
+
+        let parsed = to_parsed_recipe(&recipe);
+
+        assert_eq!(parsed.rating_value, None);
+        assert_eq!(parsed.rating_count, None);
+    }
+
+    #[test]
+    fn a_full_recipe_scores_high_confidence() {
+        let recipe = json!({
+            "name": "Full Recipe",
+            "recipeIngredient": ["2 cups flour", "1 tsp salt"],
+            "recipeInstructions": ["Mix.", "Bake."],
+            "recipeYield": "4 servings",
+            "image": "https://example.com/full.jpg",
+        });
+
+        let parsed = to_parsed_recipe(&recipe);
+
+        assert_eq!(
+            ImportConfidence::from_score(parse_confidence(&parsed)),
+            ImportConfidence::High
+        );
+    }
+
+    #[test]
+    fn a_minimal_ingredients_only_recipe_scores_lower_than_a_full_one() {
+        let minimal = json!({
+            "name": "Minimal Recipe",
+            "recipeIngredient": ["2 cups flour", "1 tsp salt"],
+        });
+        let full = json!({
+            "name": "Full Recipe",
+            "recipeIngredient": ["2 cups flour", "1 tsp salt"],
+            "recipeInstructions": ["Mix.", "Bake."],
+            "recipeYield": "4 servings",
+            "image": "https://example.com/full.jpg",
+        });
+
+        let minimal_score = parse_confidence(&to_parsed_recipe(&minimal));
+        let full_score = parse_confidence(&to_parsed_recipe(&full));
+
+        assert!(minimal_score < full_score);
+    }
+
+    #[test]
+    fn dropping_one_of_five_signals_still_reads_high_confidence() {
+        let recipe = json!({
+            "name": "Missing Only Its Image",
+            "recipeIngredient": ["2 cups flour", "1 tsp salt"],
+            "recipeInstructions": ["Mix.", "Bake."],
+            "recipeYield": "4 servings",
+        });
+
+        let score = parse_confidence(&to_parsed_recipe(&recipe));
+
+        assert_eq!(score, 0.8);
+        assert_eq!(ImportConfidence::from_score(score), ImportConfidence::High);
+    }
+
+    #[test]
+    fn dropping_two_of_five_signals_reads_medium_confidence() {
+        let recipe = json!({
+            "name": "Missing Servings And Image",
+            "recipeIngredient": ["2 cups flour", "1 tsp salt"],
+            "recipeInstructions": ["Mix.", "Bake."],
+        });
+
+        let score = parse_confidence(&to_parsed_recipe(&recipe));
+
+        assert_eq!(score, 0.6);
+        assert_eq!(ImportConfidence::from_score(score), ImportConfidence::Medium);
+    }
+
+    #[test]
+    fn confidence_increases_monotonically_as_fields_are_added() {
+        let mut recipe = json!({ "name": "Growing Recipe" });
+        let mut previous_score = parse_confidence(&to_parsed_recipe(&recipe));
+
+        for (key, value) in [
+            ("recipeIngredient", json!(["2 cups flour", "1 tsp salt"])),
+            ("recipeInstructions", json!(["Mix.", "Bake."])),
+            ("recipeYield", json!("4 servings")),
+            ("image", json!("https://example.com/growing.jpg")),
+        ] {
+            recipe[key] = value;
+            let score = parse_confidence(&to_parsed_recipe(&recipe));
+            assert!(score >= previous_score);
+            previous_score = score;
+        }
+    }
+
+    #[test]
+    fn a_warning_halves_the_otherwise_computed_score() {
+        let clean = ParsedRecipe {
+            name: "Clean Recipe".to_string(),
+            ingredient_count: 2,
+            instruction_count: 2,
+            servings: Some(4),
+            yield_unit: None,
+            has_image: true,
+            rating_value: None,
+            rating_count: None,
+            warning: None,
+        };
+        let warned = ParsedRecipe {
+            warning: Some(ParseWarning::LowConfidenceIngredients),
+            ..clean.clone()
+        };
+
+        assert_eq!(parse_confidence(&warned), parse_confidence(&clean) * 0.5);
+    }
+
+    #[test]
+    fn parses_how_to_steps_with_images() {
+        let recipe = json!({
+            "recipeInstructions": [
+                {"@type": "HowToStep", "text": "Brown the beef.", "image": "https://example.com/step1.jpg"},
+                {"@type": "HowToStep", "text": "Add beans.", "image": {"@type": "ImageObject", "url": "https://example.com/step2.jpg"}},
+            ]
+        });
+
+        let steps = parse_instructions(&recipe);
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep {
+                    text: "Brown the beef.".to_string(),
+                    image: Some("https://example.com/step1.jpg".to_string()),
+                },
+                RecipeStep {
+                    text: "Add beans.".to_string(),
+                    image: Some("https://example.com/step2.jpg".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn legacy_plain_string_instructions_still_parse() {
+        let recipe = json!({
+            "recipeInstructions": ["Brown the beef.", "Add beans."]
+        });
+
+        let steps = parse_instructions(&recipe);
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep::from("Brown the beef."),
+                RecipeStep::from("Add beans."),
+            ]
+        );
+    }
+
+    fn two_section_recipe() -> Value {
+        json!({
+            "recipeInstructions": [
+                {
+                    "@type": "HowToSection",
+                    "name": "For the sauce:",
+                    "itemListElement": [
+                        {"@type": "HowToStep", "text": "Simmer tomatoes."},
+                        {"@type": "HowToStep", "text": "Season to taste."},
+                    ]
+                },
+                {
+                    "@type": "HowToSection",
+                    "name": "For the crust:",
+                    "itemListElement": [
+                        {"@type": "HowToStep", "text": "Mix flour and water."},
+                    ]
+                },
+            ]
+        })
+    }
+
+    #[test]
+    fn section_headers_appear_before_their_respective_steps() {
+        let steps = parse_instructions(&two_section_recipe());
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep::from("For the sauce:"),
+                RecipeStep::from("Simmer tomatoes."),
+                RecipeStep::from("Season to taste."),
+                RecipeStep::from("For the crust:"),
+                RecipeStep::from("Mix flour and water."),
+            ]
+        );
+    }
+
+    #[test]
+    fn section_headers_can_be_omitted() {
+        let steps = parse_instructions_with_headers(&two_section_recipe(), false);
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep::from("Simmer tomatoes."),
+                RecipeStep::from("Season to taste."),
+                RecipeStep::from("Mix flour and water."),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_how_to_section_using_step_instead_of_item_list_element_is_still_read() {
+        let recipe = json!({
+            "recipeInstructions": [
+                {
+                    "@type": "HowToSection",
+                    "name": "For the sauce:",
+                    "step": [
+                        {"@type": "HowToStep", "text": "Simmer tomatoes."},
+                        {"@type": "HowToStep", "text": "Season to taste."},
+                    ]
+                },
+            ]
+        });
+
+        let steps = parse_instructions(&recipe);
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep::from("For the sauce:"),
+                RecipeStep::from("Simmer tomatoes."),
+                RecipeStep::from("Season to taste."),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_top_level_how_to_using_step_instead_of_an_array_is_still_read() {
+        let recipe = json!({
+            "recipeInstructions": {
+                "@type": "HowTo",
+                "step": [
+                    {"@type": "HowToStep", "text": "Brown the beef."},
+                    {"@type": "HowToStep", "text": "Add beans."},
+                ]
+            }
+        });
+
+        let steps = parse_instructions(&recipe);
+
+        assert_eq!(
+            steps,
+            vec![
+                RecipeStep::from("Brown the beef."),
+                RecipeStep::from("Add beans."),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_graph_describing_two_recipes_is_ambiguous() {
+        let graph = json!({
+            "@graph": [
+                {"@type": "Recipe", "name": "Roundup Recipe One"},
+                {"@type": "Recipe", "name": "Roundup Recipe Two"},
+            ]
+        });
+
+        let result = parse_recipe_json(&graph);
+
+        assert_eq!(result, Err(ParseError::MultipleRecipes));
+    }
+
+    #[test]
+    fn a_graph_describing_one_recipe_among_other_nodes_is_not_ambiguous() {
+        let graph = json!({
+            "@graph": [
+                {"@type": "WebPage", "name": "Page"},
+                {"@type": "Recipe", "name": "Only Recipe"},
+            ]
+        });
+
+        let parsed = parse_recipe_json(&graph).unwrap();
+
+        assert_eq!(parsed.name, "Only Recipe");
+    }
+
+    #[test]
+    fn a_clean_recipe_produces_no_low_confidence_warning() {
+        let recipe = json!({
+            "@type": "Recipe",
+            "name": "Clean Chili",
+            "recipeIngredient": ["1 lb ground beef", "1 can kidney beans", "1 can diced tomatoes"]
+        });
+
+        let parsed = parse_recipe_json(&recipe).unwrap();
+
+        assert_eq!(parsed.warning, None);
+    }
+
+    #[test]
+    fn an_ingredient_list_dominated_by_unitless_single_words_is_low_confidence() {
+        let recipe = json!({
+            "@type": "Recipe",
+            "name": "Suspicious Chili",
+            "recipeIngredient": ["Home", "About", "Contact", "1 can kidney beans"]
+        });
+
+        let parsed = parse_recipe_json(&recipe).unwrap();
+
+        assert_eq!(parsed.warning, Some(ParseWarning::LowConfidenceIngredients));
+    }
+
+    #[test]
+    fn sanitize_text_strips_null_bytes_and_vertical_tabs() {
+        let dirty = "Grandma's\u{0000} Chili\u{000B} Recipe";
+
+        assert_eq!(sanitize_text(dirty), "Grandma's Chili Recipe");
+    }
+
+    #[test]
+    fn sanitize_text_keeps_newlines_tabs_and_normal_content() {
+        let clean = "Step 1: Brown the beef.\nStep 2:\tSimmer with beans.";
+
+        assert_eq!(sanitize_text(clean), clean);
+    }
+
+    #[test]
+    fn sanitize_text_repairs_a_double_encoded_numeric_entity() {
+        assert_eq!(sanitize_text("don&amp;#39;t"), "don't");
+    }
+
+    #[test]
+    fn sanitize_text_repairs_common_mojibake() {
+        assert_eq!(sanitize_text("caf\u{00C3}\u{00A9}"), "café");
+    }
+
+    #[test]
+    fn a_recipe_name_with_embedded_control_characters_is_sanitized_on_parse() {
+        let recipe = json!({ "name": "Dirty\u{0000} Name\u{000B}" });
+
+        let parsed = to_parsed_recipe(&recipe);
+
+        assert_eq!(parsed.name, "Dirty Name");
+    }
+
+    #[test]
+    fn parse_servings_converts_a_unicode_fraction_dozen_yield() {
+        let yield_value = json!("1½ dozen");
+
+        assert_eq!(parse_servings(&yield_value), (Some(18), None));
+    }
+
+    #[test]
+    fn parse_servings_reduces_a_plain_servings_string_to_its_leading_number() {
+        let yield_value = json!("4 servings");
+
+        assert_eq!(
+            parse_servings(&yield_value),
+            (Some(4), Some("servings".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_servings_captures_the_trailing_noun_as_the_yield_unit() {
+        assert_eq!(
+            parse_servings(&json!("24 cookies")),
+            (Some(24), Some("cookies".to_string()))
+        );
+        assert_eq!(
+            parse_servings(&json!("2 loaves")),
+            (Some(2), Some("loaves".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_servings_leaves_the_yield_unit_none_for_a_bare_number() {
+        assert_eq!(parse_servings(&json!("6")), (Some(6), None));
+    }
+
+    #[test]
+    fn a_degenerate_document_over_the_parse_cap_is_rejected_before_parsing() {
+        let huge_html = "<div>".repeat(MAX_PARSE_BYTES);
+
+        let result = parse_recipe_html(&huge_html);
+
+        assert_eq!(result, Err(ParseError::TooLarge));
+    }
+
+    #[tokio::test]
+    async fn parse_recipe_html_blocking_rejects_an_oversized_document_without_hanging() {
+        let huge_html = "<div>".repeat(MAX_PARSE_BYTES);
+
+        let result = parse_recipe_html_blocking(huge_html).await;
+
+        assert!(matches!(result, Err(AppError::Parse(ParseError::TooLarge))));
+    }
+
+    #[tokio::test]
+    async fn parse_recipe_html_blocking_parses_a_normal_document_the_same_as_the_sync_version() {
+        let html = r#"<script type="application/ld+json">{"@type": "Recipe", "name": "Blocking Test Recipe"}</script>"#.to_string();
+
+        let parsed = parse_recipe_html_blocking(html).await.unwrap();
+
+        assert_eq!(parsed.name, "Blocking Test Recipe");
+    }
+
+    #[test]
+    fn a_script_tag_holding_a_json_array_of_two_objects_still_finds_the_recipe() {
+        let html = r#"<script type="application/ld+json">[
+            {"@type": "WebPage", "name": "Page"},
+            {"@type": "Recipe", "name": "Array Block Recipe"}
+        ]</script>"#;
+
+        let parsed = parse_recipe_html(html).unwrap();
+
+        assert_eq!(parsed.name, "Array Block Recipe");
+    }
+
+    #[test]
+    fn a_script_tag_holding_two_concatenated_objects_still_finds_the_recipe() {
+        let html = r#"<script type="application/ld+json">
+            {"@type": "WebPage", "name": "Page"}
+            {"@type": "Recipe", "name": "Concatenated Block Recipe"}
+        </script>"#;
+
+        let parsed = parse_recipe_html(html).unwrap();
+
+        assert_eq!(parsed.name, "Concatenated Block Recipe");
+    }
+
+    #[test]
+    fn a_script_tag_holding_a_json_array_of_two_recipes_is_ambiguous() {
+        let html = r#"<script type="application/ld+json">[
+            {"@type": "Recipe", "name": "Roundup Recipe One"},
+            {"@type": "Recipe", "name": "Roundup Recipe Two"}
+        ]</script>"#;
+
+        let result = parse_recipe_html(html);
+
+        assert_eq!(result, Err(ParseError::MultipleRecipes));
+    }
+
+    #[test]
+    fn jsonld_escaped_inside_a_template_is_found_once_no_script_tag_has_it() {
+        let html = r#"<template id="recipe-data">
+            &lt;script type=&quot;application/ld+json&quot;&gt;
+            {&quot;@type&quot;: &quot;Recipe&quot;, &quot;name&quot;: &quot;Template Stashed Recipe&quot;}
+            &lt;/script&gt;
+        </template>"#;
+
+        let parsed = parse_recipe_html(html).unwrap();
+
+        assert_eq!(parsed.name, "Template Stashed Recipe");
+    }
+
+    #[test]
+    fn extract_tags_captures_every_value_in_a_recipe_category_array() {
+        let recipe = json!({
+            "recipeCategory": ["Dinner", "Main Course"],
+        });
+
+        assert_eq!(
+            extract_tags(&recipe),
+            vec!["Dinner".to_string(), "Main Course".to_string()]
+        );
+    }
+
+    #[test]
+    fn extract_tags_captures_every_value_in_a_recipe_cuisine_array() {
+        let recipe = json!({
+            "recipeCuisine": ["Italian", "Mediterranean"],
+        });
+
+        assert_eq!(
+            extract_tags(&recipe),
+            vec!["Italian".to_string(), "Mediterranean".to_string()]
+        );
+    }
+
+    #[test]
+    fn extract_tags_splits_a_comma_separated_keywords_string() {
+        let recipe = json!({
+            "keywords": "quick, easy, weeknight",
+        });
+
+        assert_eq!(
+            extract_tags(&recipe),
+            vec![
+                "quick".to_string(),
+                "easy".to_string(),
+                "weeknight".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_tags_combines_all_three_fields_and_dedupes_case_insensitively() {
+        let recipe = json!({
+            "recipeCategory": "Dinner",
+            "recipeCuisine": "Italian",
+            "keywords": "dinner, pasta",
+        });
+
+        assert_eq!(
+            extract_tags(&recipe),
+            vec!["Dinner".to_string(), "Italian".to_string(), "pasta".to_string()]
+        );
+    }
+}
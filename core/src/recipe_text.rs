@@ -0,0 +1,150 @@
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::{Recipe, RecipeIngredientExport, RecipeStep};
+use crate::utils::units::format_quantity_as_fraction;
+
+/// Scales every ingredient's quantity from `base_servings` to
+/// `target_servings`, for a "make this for a different crowd size"
+/// preview. Guards `base_servings == 0` with a [`AppError::Validation`]
+/// rather than dividing by it, since a recipe with no servings has no
+/// scaling factor to derive.
+pub fn scale_ingredients(
+    ingredients: &[RecipeIngredientExport],
+    base_servings: i64,
+    target_servings: i64,
+) -> Result<Vec<RecipeIngredientExport>, AppError> {
+    if base_servings == 0 {
+        return Err(AppError::Validation(
+            "cannot scale a recipe with zero base servings".to_string(),
+        ));
+    }
+
+    let factor = target_servings as f64 / base_servings as f64;
+    Ok(ingredients
+        .iter()
+        .map(|ingredient| RecipeIngredientExport {
+            quantity: ingredient.quantity * factor,
+            ..ingredient.clone()
+        })
+        .collect())
+}
+
+/// Renders a clean, print-friendly text block for `recipe`: name, servings,
+/// ingredients with fraction-formatted quantities, then numbered
+/// instructions. Meant for scaled previews, so it takes the already-scaled
+/// ingredients rather than reading `recipe`'s own.
+pub fn render_recipe_text(
+    recipe: &Recipe,
+    ingredients: &[RecipeIngredientExport],
+    servings: i64,
+) -> Result<String, AppError> {
+    let instructions: Vec<RecipeStep> = serde_json::from_str(&recipe.instructions)
+        .map_err(|e| AppError::Internal(format!("failed to parse instructions: {e}")))?;
+
+    let mut text = format!("{}\n\nServings: {servings}\n\nIngredients:\n", recipe.name);
+    for ingredient in ingredients {
+        text.push_str(&format!(
+            "- {} {} {}\n",
+            format_quantity_as_fraction(ingredient.quantity),
+            ingredient.unit,
+            ingredient.name
+        ));
+    }
+
+    text.push_str("\nInstructions:\n");
+    for (index, step) in instructions.iter().enumerate() {
+        text.push_str(&format!("{}. {}\n", index + 1, step.text));
+    }
+
+    Ok(text)
+}
+
+/// Fetches `id`, scales its ingredients to `target_servings`, and renders
+/// the result as print-friendly text — see [`scale_ingredients`] and
+/// [`render_recipe_text`].
+pub async fn scaled_recipe_text(
+    pool: &SqlitePool,
+    id: &str,
+    target_servings: i64,
+) -> Result<String, AppError> {
+    let recipe = db::recipes::get_recipe_by_id(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("recipe '{id}' does not exist")))?;
+    let ingredients = db::recipes::get_recipe_ingredients(pool, id).await?;
+
+    let scaled = scale_ingredients(&ingredients, recipe.servings, target_servings)?;
+    render_recipe_text(&recipe, &scaled, target_servings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::pool::init_db_for_test;
+    use crate::db::recipes::{add_recipe_ingredient, create_recipe};
+    use crate::models::RecipeInput;
+
+    fn sample_recipe_input(name: &str) -> RecipeInput {
+        RecipeInput {
+            name: name.to_string(),
+            description: None,
+            servings: 4,
+            yield_unit: None,
+            prep_time: None,
+            cook_time: None,
+            instructions: vec!["Mix".into(), "Bake".into()],
+            image_path: None,
+            source_url: None,
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn scaled_recipe_text_scales_quantities_and_formats_fractions() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("Scaling Test Pancakes"))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &recipe.id,
+            &RecipeIngredientExport {
+                name: "scaling-test flour".to_string(),
+                quantity: 1.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let text = scaled_recipe_text(&pool, &recipe.id, 6).await.unwrap();
+
+        assert!(text.contains("Servings: 6"));
+        assert!(text.contains("1 1/2 cup scaling-test flour"));
+        assert!(text.contains("1. Mix"));
+        assert!(text.contains("2. Bake"));
+    }
+
+    #[tokio::test]
+    async fn scale_ingredients_rejects_zero_base_servings() {
+        let ingredients = [RecipeIngredientExport {
+            name: "zero-base-test salt".to_string(),
+            quantity: 1.0,
+            unit: "tsp".to_string(),
+            notes: None,
+            sort_order: 0,
+        }];
+
+        let result = scale_ingredients(&ingredients, 0, 4);
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDate;
+
+use crate::error::AppError;
+
+/// Checks that `s` is a calendar date in canonical `YYYY-MM-DD` form (e.g.
+/// `week_start`, `date`, `start_date`, `end_date`), returning it unchanged
+/// on success. These strings flow straight into SQL date comparisons
+/// (`BETWEEN ? AND ?`, equality) without any further parsing, so a
+/// differently-formatted date like `"1/15/2025"` or an out-of-range one
+/// like `"2025-13-40"` wouldn't error there — it would just silently
+/// compare as a string and return wrong (usually empty) results. Command
+/// handlers call this at the boundary to turn that into a clear
+/// [`AppError::Validation`] instead.
+pub fn validate_ymd(s: &str) -> Result<String, AppError> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|_| s.to_string())
+        .map_err(|_| AppError::Validation(format!("'{s}' is not a valid YYYY-MM-DD date")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_a_canonical_date() {
+        assert_eq!(validate_ymd("2025-01-15").unwrap(), "2025-01-15");
+    }
+
+    #[test]
+    fn rejects_a_non_canonical_format() {
+        let err = validate_ymd("1/15/2025").unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn rejects_an_out_of_range_date() {
+        let err = validate_ymd("2025-13-40").unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+}
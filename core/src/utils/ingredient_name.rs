@@ -0,0 +1,87 @@
+/// A small table of plurals that the trailing-`s`/`es`/`ies` rules below
+/// get wrong, so they're special-cased instead.
+const IRREGULAR_PLURALS: [(&str, &str); 4] = [
+    ("leaves", "leaf"),
+    ("knives", "knife"),
+    ("loaves", "loaf"),
+    ("halves", "half"),
+];
+
+/// Reduces an ingredient name to a grouping key that merges common English
+/// plurals with their singular form (`"eggs"`/`"egg"`, `"tomatoes"`/
+/// `"tomato"`), so aggregation doesn't treat them as different ingredients.
+/// Deliberately conservative: a bare trailing `"s"` is only stripped when
+/// doing so wouldn't leave a double-`s` ending (`"molasses"` stays put
+/// rather than becoming `"molasse"`), since over-merging unrelated words is
+/// worse than under-merging a rare plural this doesn't catch.
+pub fn singularize_ingredient_name(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+
+    if let Some((_, singular)) = IRREGULAR_PLURALS
+        .iter()
+        .find(|(plural, _)| *plural == lower)
+    {
+        return singular.to_string();
+    }
+
+    if let Some(stem) = lower.strip_suffix("ies") {
+        if stem.len() > 1 {
+            return format!("{stem}y");
+        }
+    }
+
+    if let Some(stem) = lower.strip_suffix("es") {
+        if !stem.is_empty() && !stem.ends_with('s') {
+            return stem.to_string();
+        }
+    }
+
+    if let Some(stem) = lower.strip_suffix('s') {
+        if !stem.is_empty() && !stem.ends_with('s') && !lower.ends_with("sses") {
+            return stem.to_string();
+        }
+    }
+
+    lower
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_eggs_and_egg() {
+        assert_eq!(
+            singularize_ingredient_name("eggs"),
+            singularize_ingredient_name("egg")
+        );
+    }
+
+    #[test]
+    fn merges_tomatoes_and_tomato() {
+        assert_eq!(
+            singularize_ingredient_name("tomatoes"),
+            singularize_ingredient_name("tomato")
+        );
+    }
+
+    #[test]
+    fn leaves_molasses_alone() {
+        assert_eq!(singularize_ingredient_name("molasses"), "molasses");
+    }
+
+    #[test]
+    fn merges_an_irregular_plural() {
+        assert_eq!(singularize_ingredient_name("leaves"), "leaf");
+    }
+
+    #[test]
+    fn merges_a_y_plural() {
+        assert_eq!(singularize_ingredient_name("cherries"), "cherry");
+    }
+
+    #[test]
+    fn is_case_and_whitespace_insensitive() {
+        assert_eq!(singularize_ingredient_name(" Eggs "), "egg");
+    }
+}
@@ -0,0 +1,4 @@
+pub mod dates;
+pub mod ingredient_name;
+pub mod redact;
+pub mod units;
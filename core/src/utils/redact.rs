@@ -0,0 +1,64 @@
+const MAX_LEN: usize = 50;
+
+/// Truncates `s` to at most [`MAX_LEN`] characters for logging, so a long
+/// recipe name or URL doesn't flood the log line. Truncates on a `char`
+/// boundary rather than a fixed byte index, since these strings come
+/// straight from user input (recipe names, URLs) and can contain multi-byte
+/// UTF-8 characters that a byte-index slice could split mid-character and
+/// panic on.
+///
+/// When `redact_content` is set (a shared/kiosk machine where even a short
+/// recipe name might be sensitive), the value itself is never shown — only
+/// its length, via [`redact_user_content`].
+pub fn redact_string(s: &str, redact_content: bool) -> String {
+    if redact_content {
+        return redact_user_content(s);
+    }
+    match s.char_indices().nth(MAX_LEN) {
+        Some((boundary, _)) => format!("{}…", &s[..boundary]),
+        None => s.to_string(),
+    }
+}
+
+/// Replaces `s` with a placeholder naming only its length, for logging on a
+/// shared machine where even a short value shouldn't appear in the log file.
+fn redact_user_content(s: &str) -> String {
+    format!("<{} chars>", s.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_short_strings_untouched() {
+        assert_eq!(redact_string("pancakes", false), "pancakes");
+    }
+
+    #[test]
+    fn truncates_long_ascii_strings() {
+        let input = "a".repeat(100);
+        let result = redact_string(&input, false);
+        assert_eq!(result.chars().count(), MAX_LEN + 1);
+        assert!(result.ends_with('…'));
+    }
+
+    #[test]
+    fn does_not_panic_on_long_multibyte_strings() {
+        let input = "é".repeat(150);
+        let result = redact_string(&input, false);
+        assert_eq!(result.chars().count(), MAX_LEN + 1);
+        assert!(result.ends_with('…'));
+    }
+
+    #[test]
+    fn with_redact_content_a_short_string_becomes_length_only() {
+        assert_eq!(redact_string("pancakes", true), "<8 chars>");
+    }
+
+    #[test]
+    fn with_redact_content_a_long_string_is_still_length_only() {
+        let input = "a".repeat(100);
+        assert_eq!(redact_string(&input, true), "<100 chars>");
+    }
+}
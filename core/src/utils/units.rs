@@ -0,0 +1,646 @@
+/// Parses a free-text measurement like `"2 1/2 cups"` into its quantity and
+/// unit, so the frontend can store them separately without duplicating this
+/// parsing logic in JS. Understands plain numbers, decimals, simple
+/// fractions (`"1/2"`), and mixed numbers (`"2 1/2"`); anything after the
+/// quantity is taken as the unit and singularized (`"cups"` -> `"cup"`). A
+/// bare number with no trailing unit returns an empty unit string.
+///
+/// A parenthetical package size embedded in the remainder (`"1 (14.5 oz)
+/// can diced tomatoes"`) is pulled out into the third element rather than
+/// left mixed into the unit string, so the can's actual volume/weight is
+/// available for aggregation; a remainder with no parenthetical returns
+/// `None` there and is otherwise parsed exactly as before.
+///
+/// Some structured ingredient lists invert this order (`"Flour - 2 cups"`,
+/// `"Salt: 1 tsp"`) — see [`split_inverted_measurement`]. When that shape
+/// is detected, the quantity is read from the right-hand side and the
+/// second element becomes the left-hand side (the ingredient name)
+/// instead of a unit, since that's what callers that feed a whole
+/// ingredient line through this function actually want back.
+pub fn parse_measurement(input: &str) -> (f64, String, Option<(f64, String)>) {
+    let normalized = normalize_unicode_fractions(input);
+
+    if let Some((name, rest)) = split_inverted_measurement(&normalized) {
+        let (quantity, _unit, package_size) = parse_measurement_tokens(rest);
+        return (quantity, name.to_string(), package_size);
+    }
+
+    parse_measurement_tokens(&normalized)
+}
+
+/// Detects an inverted ingredient line — `"name: quantity unit"` or
+/// `"name - quantity unit"` — that some structured formats (e.g. a
+/// JSON-LD `recipeIngredient` array) write the wrong way around for
+/// [`parse_measurement_tokens`]'s quantity-first assumption. A separator
+/// (`":"` or `" - "`) followed by a token [`parse_number_token`] accepts
+/// is taken as the boundary; anything else (a bare name, a name
+/// containing a colon that isn't followed by a number, a quantity-first
+/// line with no separator at all) returns `None` and falls through to the
+/// normal path unaffected.
+fn split_inverted_measurement(input: &str) -> Option<(&str, &str)> {
+    for separator in [":", " - "] {
+        let Some(index) = input.find(separator) else {
+            continue;
+        };
+        let name = input[..index].trim();
+        let rest = input[index + separator.len()..].trim_start();
+        let rest_starts_with_a_number = rest
+            .split_whitespace()
+            .next()
+            .is_some_and(|token| parse_number_token(token.trim_start_matches('~')).is_some());
+
+        let name_is_itself_a_number = parse_number_token(name.trim_start_matches('~')).is_some();
+
+        if !name.is_empty() && rest_starts_with_a_number && !name_is_itself_a_number {
+            return Some((name, rest));
+        }
+    }
+    None
+}
+
+fn parse_measurement_tokens(input: &str) -> (f64, String, Option<(f64, String)>) {
+    let mut tokens: Vec<&str> = input.split_whitespace().collect();
+    strip_leading_hedge_words(&mut tokens);
+    if tokens.is_empty() {
+        return (0.0, String::new(), None);
+    }
+
+    let mut quantity = 0.0;
+    let mut consumed = 0;
+
+    if let Some(first) = parse_number_token(tokens[0].trim_start_matches('~')) {
+        quantity = first;
+        consumed = 1;
+
+        if let Some(second) = tokens.get(1) {
+            if second.contains('/') {
+                if let Some(fraction) = parse_number_token(second) {
+                    quantity += fraction;
+                    consumed = 2;
+                }
+            }
+        }
+    }
+
+    let (remainder, package_size) = extract_package_size(&tokens[consumed..].join(" "));
+    let unit = singularize(&remainder);
+    (quantity, unit, package_size)
+}
+
+/// Pulls a `"(<quantity> <unit>)"` parenthetical size (e.g. `"(14.5 oz)"`
+/// out of `"1 (14.5 oz) can diced tomatoes"`) out of `text`, returning the
+/// text with that parenthetical removed alongside the size it described.
+/// A parenthetical that isn't `<number> <unit...>` is left in place and
+/// treated as ordinary text instead, since it's more likely a note
+/// (`"(optional)"`) than a size.
+fn extract_package_size(text: &str) -> (String, Option<(f64, String)>) {
+    let Some(start) = text.find('(') else {
+        return (text.to_string(), None);
+    };
+    let Some(end) = text[start..].find(')') else {
+        return (text.to_string(), None);
+    };
+    let end = start + end;
+
+    let inner = text[start + 1..end].trim();
+    let mut inner_tokens = inner.split_whitespace();
+    let Some(quantity) = inner_tokens.next().and_then(parse_number_token) else {
+        return (text.to_string(), None);
+    };
+    let unit: String = inner_tokens.collect::<Vec<_>>().join(" ");
+    if unit.is_empty() {
+        return (text.to_string(), None);
+    }
+
+    let without_parenthetical = format!("{}{}", &text[..start], &text[end + 1..]);
+    let cleaned = without_parenthetical
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (cleaned, Some((quantity, singularize(&unit))))
+}
+
+/// Pulls a trailing `"(<note>)"` parenthetical prep clause (e.g. the
+/// `"(minced)"` in `"garlic (minced)"`) off the end of `text`, returning the
+/// text with it removed alongside the note by itself. Only a parenthetical
+/// at the very end of `text` counts — one elsewhere is more likely part of
+/// the ingredient name itself (`"salt (or to taste) and pepper"`) than a
+/// trailing clause describing it.
+pub fn extract_prep_note(text: &str) -> (String, Option<String>) {
+    let trimmed = text.trim_end();
+    if !trimmed.ends_with(')') {
+        return (text.to_string(), None);
+    }
+    let Some(start) = trimmed.rfind('(') else {
+        return (text.to_string(), None);
+    };
+
+    let note = trimmed[start + 1..trimmed.len() - 1].trim();
+    if note.is_empty() {
+        return (text.to_string(), None);
+    }
+
+    let cleaned = trimmed[..start].trim_end().to_string();
+    (cleaned, Some(note.to_string()))
+}
+
+/// Leading hedge words ("about 2 cups", "roughly 1 lb") don't carry a
+/// quantity themselves but trip up [`parse_number_token`] if left in place,
+/// so they're dropped before quantity parsing starts. A lone `"~"` token is
+/// treated the same way; a `~` fused onto the quantity itself (`"~1 tsp"`)
+/// is handled separately where the quantity token is parsed.
+const HEDGE_WORDS: [&str; 5] = ["about", "approximately", "roughly", "around", "~"];
+
+fn strip_leading_hedge_words(tokens: &mut Vec<&str>) {
+    while let Some(first) = tokens.first() {
+        if HEDGE_WORDS.contains(&first.to_lowercase().as_str()) {
+            tokens.remove(0);
+        } else {
+            break;
+        }
+    }
+}
+
+pub(crate) fn parse_number_token(token: &str) -> Option<f64> {
+    if let Some((numerator, denominator)) = token.split_once('/') {
+        let numerator: f64 = numerator.parse().ok()?;
+        let denominator: f64 = denominator.parse().ok()?;
+        if denominator == 0.0 {
+            return None;
+        }
+        return Some(numerator / denominator);
+    }
+    token.parse().ok()
+}
+
+/// The Unicode "vulgar fraction" characters (`½`, `¼`, ...) sites
+/// occasionally write instead of the ASCII `"1/2"` form, mapped to the
+/// ASCII fraction [`parse_number_token`] already understands.
+const UNICODE_FRACTIONS: &[(char, &str)] = &[
+    ('¼', "1/4"),
+    ('½', "1/2"),
+    ('¾', "3/4"),
+    ('⅓', "1/3"),
+    ('⅔', "2/3"),
+    ('⅕', "1/5"),
+    ('⅖', "2/5"),
+    ('⅗', "3/5"),
+    ('⅘', "4/5"),
+    ('⅙', "1/6"),
+    ('⅚', "5/6"),
+    ('⅐', "1/7"),
+    ('⅛', "1/8"),
+    ('⅜', "3/8"),
+    ('⅝', "5/8"),
+    ('⅞', "7/8"),
+    ('⅑', "1/9"),
+    ('⅒', "1/10"),
+];
+
+/// Rewrites Unicode vulgar fractions in `text` to the ASCII `"N/D"` form,
+/// so every place that parses a quantity — ingredient amounts, servings,
+/// durations — only has to understand the ASCII spelling. A fraction
+/// glued directly onto a preceding digit (`"1½"`, as sites commonly write
+/// it) becomes a space-separated mixed number (`"1 1/2"`) rather than
+/// `"11/2"`, so it's still read as one-and-a-half rather than eleven
+/// halves.
+pub(crate) fn normalize_unicode_fractions(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match UNICODE_FRACTIONS.iter().find(|(ch, _)| *ch == c) {
+            Some((_, ascii)) => {
+                if out.chars().last().is_some_and(|prev| prev.is_ascii_digit()) {
+                    out.push(' ');
+                }
+                out.push_str(ascii);
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+fn singularize(unit: &str) -> String {
+    if unit.len() > 1 && unit.ends_with('s') && !unit.ends_with("ss") {
+        unit[..unit.len() - 1].to_string()
+    } else {
+        unit.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Count,
+    Volume,
+    Weight,
+}
+
+/// Returns the unit's measurement dimension and its size relative to the
+/// dimension's base unit (teaspoons for volume, grams for weight), so two
+/// quantities in different-but-compatible units can be summed.
+fn dimension_and_factor(unit: &str) -> Option<(Dimension, f64)> {
+    match unit {
+        "" => Some((Dimension::Count, 1.0)),
+        "tsp" | "teaspoon" => Some((Dimension::Volume, 1.0)),
+        "tbsp" | "tablespoon" => Some((Dimension::Volume, 3.0)),
+        "cup" => Some((Dimension::Volume, 48.0)),
+        "pint" => Some((Dimension::Volume, 96.0)),
+        "quart" => Some((Dimension::Volume, 192.0)),
+        "gallon" => Some((Dimension::Volume, 768.0)),
+        "ml" | "milliliter" => Some((Dimension::Volume, 0.202_884)),
+        "l" | "liter" => Some((Dimension::Volume, 202.884)),
+        "g" | "gram" => Some((Dimension::Weight, 1.0)),
+        "kg" | "kilogram" => Some((Dimension::Weight, 1000.0)),
+        "oz" | "ounce" => Some((Dimension::Weight, 28.3495)),
+        "lb" | "pound" => Some((Dimension::Weight, 453.592)),
+        _ => None,
+    }
+}
+
+/// Converts `quantity` of `from_unit` into `to_unit`, returning `None` when
+/// the units are unrecognized or in different dimensions (e.g. volume vs
+/// weight) — callers should treat `None` as "not mergeable", not an error.
+pub fn convert_quantity(quantity: f64, from_unit: &str, to_unit: &str) -> Option<f64> {
+    let from = singularize(&from_unit.trim().to_lowercase());
+    let to = singularize(&to_unit.trim().to_lowercase());
+    let (from_dimension, from_factor) = dimension_and_factor(&from)?;
+    let (to_dimension, to_factor) = dimension_and_factor(&to)?;
+    if from_dimension != to_dimension {
+        return None;
+    }
+    Some(quantity * from_factor / to_factor)
+}
+
+/// Like [`convert_quantity`], but rounds the result to `decimals`
+/// fractional digits. [`convert_quantity`] itself stays exact so chained
+/// internal math doesn't compound rounding error; this wrapper is for
+/// display boundaries, where a raw float would otherwise surface as
+/// something like "0.9999999 cups" after a round-trip conversion.
+pub fn convert_quantity_rounded(
+    quantity: f64,
+    from_unit: &str,
+    to_unit: &str,
+    decimals: u32,
+) -> Option<f64> {
+    convert_quantity(quantity, from_unit, to_unit).map(|value| round_to_decimals(value, decimals))
+}
+
+/// Unit pairs eligible for promotion from the smaller unit to the larger
+/// one once a quantity reaches `threshold` smaller-units (`1000 g` -> `1
+/// kg`, `1000 ml` -> `1 l`).
+const PROMOTIONS: [(&str, &str, f64); 2] = [("g", "kg", 1000.0), ("ml", "l", 1000.0)];
+
+/// Tames floating-point error in an aggregated quantity (`1.3333333` ->
+/// `1.33`) by rounding to 2 fractional digits, and for weight/volume
+/// promotes to the next unit up via [`convert_quantity`] once the value
+/// crosses its [`PROMOTIONS`] threshold, so `"1500 g"` renders as the
+/// friendlier `"1.5 kg"`. Units with no promotion entry just get rounded in
+/// place.
+pub fn normalize_quantity(quantity: f64, unit: &str) -> (f64, String) {
+    let normalized_unit = singularize(&unit.trim().to_lowercase());
+
+    for (smaller, larger, threshold) in PROMOTIONS {
+        if normalized_unit == smaller && quantity.abs() >= threshold {
+            if let Some(converted) = convert_quantity(quantity, smaller, larger) {
+                return (round_to_hundredths(converted), larger.to_string());
+            }
+        }
+    }
+
+    (round_to_hundredths(quantity), unit.to_string())
+}
+
+fn round_to_hundredths(value: f64) -> f64 {
+    round_to_decimals(value, 2)
+}
+
+/// How an aggregated count-category quantity (eggs, cans — anything with no
+/// unit) gets rounded before it's shown as "how many to buy". Volume and
+/// weight quantities are unaffected regardless of policy; see
+/// [`round_count_quantity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountRoundingPolicy {
+    /// Round up to the next whole number — you can't buy half an egg, so
+    /// "2.5 eggs" becomes "3".
+    Up,
+    /// Round to the nearest whole number.
+    Nearest,
+    /// Leave the quantity fractional.
+    None,
+}
+
+/// Applies `policy` to `quantity`, but only when `unit` is the empty
+/// "count" unit (see [`dimension_and_factor`]) — a fractional `2.5 cups` of
+/// flour is perfectly buyable, so volume and weight quantities pass through
+/// unchanged no matter the policy.
+pub fn round_count_quantity(quantity: f64, unit: &str, policy: CountRoundingPolicy) -> f64 {
+    if !unit.trim().is_empty() {
+        return quantity;
+    }
+
+    match policy {
+        CountRoundingPolicy::Up => quantity.ceil(),
+        CountRoundingPolicy::Nearest => quantity.round(),
+        CountRoundingPolicy::None => quantity,
+    }
+}
+
+/// Common cooking fraction denominators, checked smallest first so a value
+/// close to a simpler fraction (`1/2`) is preferred over a more awkward one
+/// that also happens to fit (`4/8`).
+const FRACTION_DENOMINATORS: [i64; 4] = [2, 3, 4, 8];
+
+/// How close `quantity`'s fractional part has to land to a candidate
+/// fraction to use it, rather than falling back to decimal.
+const FRACTION_EPSILON: f64 = 0.01;
+
+/// Renders `quantity` as a whole number plus the simplest common cooking
+/// fraction that's within [`FRACTION_EPSILON`] of it (`1.5` -> `"1 1/2"`,
+/// `0.33` -> `"1/3"`), falling back to two decimal places when no such
+/// fraction fits closely enough. For print-friendly recipe text, where
+/// "1 1/2 cups" reads far more naturally than "1.5 cups".
+pub fn format_quantity_as_fraction(quantity: f64) -> String {
+    let whole = quantity.trunc() as i64;
+    let fractional = (quantity - quantity.trunc()).abs();
+
+    if fractional < FRACTION_EPSILON {
+        return whole.to_string();
+    }
+
+    for denominator in FRACTION_DENOMINATORS {
+        let numerator = (fractional * denominator as f64).round() as i64;
+        if numerator == 0 || numerator == denominator {
+            continue;
+        }
+        if (fractional - numerator as f64 / denominator as f64).abs() < FRACTION_EPSILON {
+            let sign = if quantity < 0.0 { "-" } else { "" };
+            return if whole == 0 {
+                format!("{sign}{numerator}/{denominator}")
+            } else {
+                format!("{sign}{} {numerator}/{denominator}", whole.abs())
+            };
+        }
+    }
+
+    format!("{quantity:.2}")
+}
+
+/// Formats `quantity`/`unit` for display by chaining [`normalize_quantity`]
+/// (kg/L promotion, rounding) with [`format_quantity_as_fraction`]
+/// (fraction rendering), so every export path — CSV, plain-text, the
+/// aggregated list view — shows the same `("1.5", "kg")` for `1500.0` `"g"`
+/// instead of each one re-deriving its own formatting and drifting apart.
+pub fn format_quantity_for_display(quantity: f64, unit: &str) -> (String, String) {
+    let (normalized_quantity, normalized_unit) = normalize_quantity(quantity, unit);
+    (
+        format_quantity_as_fraction(normalized_quantity),
+        normalized_unit,
+    )
+}
+
+fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mixed_fraction_with_unit() {
+        assert_eq!(
+            parse_measurement("2 1/2 cups"),
+            (2.5, "cup".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn parses_plain_number_with_unit() {
+        assert_eq!(parse_measurement("300 g"), (300.0, "g".to_string(), None));
+    }
+
+    #[test]
+    fn parses_bare_number_with_no_unit() {
+        assert_eq!(parse_measurement("3"), (3.0, String::new(), None));
+    }
+
+    #[test]
+    fn parses_a_name_dash_quantity_line() {
+        assert_eq!(
+            parse_measurement("Flour - 2 cups"),
+            (2.0, "Flour".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn parses_a_name_colon_quantity_line() {
+        assert_eq!(
+            parse_measurement("Salt: 1 tsp"),
+            (1.0, "Salt".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn a_normal_quantity_first_line_is_unaffected() {
+        assert_eq!(
+            parse_measurement("2 cups flour"),
+            (2.0, "cups flour".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn parses_simple_fraction_without_whole_number() {
+        assert_eq!(parse_measurement("1/2 tsp"), (0.5, "tsp".to_string(), None));
+    }
+
+    #[test]
+    fn parses_multi_word_unit() {
+        assert_eq!(
+            parse_measurement("2 fl oz"),
+            (2.0, "fl oz".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn strips_a_leading_hedge_word_before_parsing_quantity() {
+        assert_eq!(
+            parse_measurement("about 2 cups flour"),
+            (2.0, "cups flour".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn strips_a_leading_tilde_fused_onto_the_quantity() {
+        assert_eq!(
+            parse_measurement("~1 tsp salt"),
+            (1.0, "tsp salt".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn parses_normally_with_no_hedge_word() {
+        assert_eq!(parse_measurement("3 eggs"), (3.0, "egg".to_string(), None));
+    }
+
+    #[test]
+    fn captures_a_parenthetical_package_size() {
+        assert_eq!(
+            parse_measurement("1 (14.5 oz) can diced tomatoes"),
+            (
+                1.0,
+                "can diced tomatoe".to_string(),
+                Some((14.5, "oz".to_string()))
+            )
+        );
+    }
+
+    #[test]
+    fn a_line_without_a_parenthetical_has_no_package_size() {
+        assert_eq!(
+            parse_measurement("1 can diced tomatoes"),
+            (1.0, "can diced tomatoe".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn extract_prep_note_pulls_a_trailing_parenthetical_off_the_name() {
+        assert_eq!(
+            extract_prep_note("garlic (minced)"),
+            ("garlic".to_string(), Some("minced".to_string()))
+        );
+    }
+
+    #[test]
+    fn extract_prep_note_leaves_a_name_with_no_parenthetical_alone() {
+        assert_eq!(extract_prep_note("garlic"), ("garlic".to_string(), None));
+    }
+
+    #[test]
+    fn extract_prep_note_ignores_a_parenthetical_that_is_not_at_the_end() {
+        assert_eq!(
+            extract_prep_note("salt (or to taste) and pepper"),
+            ("salt (or to taste) and pepper".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn converts_between_compatible_volume_units() {
+        assert_eq!(convert_quantity(1.0, "tbsp", "tsp"), Some(3.0));
+    }
+
+    #[test]
+    fn refuses_to_convert_across_dimensions() {
+        assert_eq!(convert_quantity(1.0, "cup", "lb"), None);
+    }
+
+    #[test]
+    fn matching_units_convert_with_identity_factor() {
+        assert_eq!(convert_quantity(2.0, "cups", "cup"), Some(2.0));
+    }
+
+    #[test]
+    fn promotes_grams_to_kilograms_past_the_threshold() {
+        assert_eq!(normalize_quantity(1500.0, "g"), (1.5, "kg".to_string()));
+    }
+
+    #[test]
+    fn stays_in_grams_below_the_threshold() {
+        assert_eq!(normalize_quantity(500.0, "g"), (500.0, "g".to_string()));
+    }
+
+    #[test]
+    fn round_count_quantity_rounds_a_count_up_under_the_up_policy() {
+        assert_eq!(
+            round_count_quantity(2.5, "", CountRoundingPolicy::Up),
+            3.0
+        );
+    }
+
+    #[test]
+    fn round_count_quantity_leaves_volume_alone_under_any_policy() {
+        assert_eq!(
+            round_count_quantity(2.5, "cup", CountRoundingPolicy::Up),
+            2.5
+        );
+    }
+
+    #[test]
+    fn round_count_quantity_leaves_a_count_fractional_under_the_none_policy() {
+        assert_eq!(
+            round_count_quantity(2.5, "", CountRoundingPolicy::None),
+            2.5
+        );
+    }
+
+    #[test]
+    fn format_quantity_for_display_promotes_and_renders_a_fraction() {
+        let (quantity, unit) = format_quantity_for_display(1500.0, "g");
+        assert_eq!(quantity, "1 1/2");
+        assert_eq!(unit, "kg");
+    }
+
+    #[test]
+    fn format_quantity_for_display_leaves_an_unpromoted_unit_alone() {
+        let (quantity, unit) = format_quantity_for_display(1.0, "can");
+        assert_eq!(quantity, "1");
+        assert_eq!(unit, "can");
+    }
+
+    #[test]
+    fn rounds_floating_point_error_cleanly() {
+        assert_eq!(
+            normalize_quantity(1.3333333, "cup"),
+            (1.33, "cup".to_string())
+        );
+    }
+
+    #[test]
+    fn a_cup_to_ml_to_cup_round_trip_is_very_close_to_the_original() {
+        let ml = convert_quantity(1.0, "cup", "ml").unwrap();
+        let back = convert_quantity(ml, "ml", "cup").unwrap();
+        assert!((back - 1.0).abs() < 1e-9, "round trip drifted to {back}");
+    }
+
+    #[test]
+    fn convert_quantity_rounded_yields_clean_values_at_two_decimals() {
+        let ml = convert_quantity(1.0, "cup", "ml").unwrap();
+        let back = convert_quantity_rounded(ml, "ml", "cup", 2);
+        assert_eq!(back, Some(1.0));
+    }
+
+    #[test]
+    fn normalize_unicode_fractions_converts_a_glued_on_mixed_number() {
+        assert_eq!(normalize_unicode_fractions("2½"), "2 1/2");
+    }
+
+    #[test]
+    fn normalize_unicode_fractions_converts_a_standalone_fraction() {
+        assert_eq!(normalize_unicode_fractions("½ cup"), "1/2 cup");
+    }
+
+    #[test]
+    fn parse_measurement_understands_a_glued_on_unicode_mixed_number() {
+        assert_eq!(parse_measurement("2½ cups"), (2.5, "cup".to_string(), None));
+    }
+
+    #[test]
+    fn format_quantity_as_fraction_renders_a_mixed_number() {
+        assert_eq!(format_quantity_as_fraction(1.5), "1 1/2");
+    }
+
+    #[test]
+    fn format_quantity_as_fraction_renders_a_bare_fraction() {
+        assert_eq!(format_quantity_as_fraction(0.25), "1/4");
+    }
+
+    #[test]
+    fn format_quantity_as_fraction_renders_a_whole_number() {
+        assert_eq!(format_quantity_as_fraction(3.0), "3");
+    }
+
+    #[test]
+    fn format_quantity_as_fraction_falls_back_to_decimals_for_an_uncommon_value() {
+        assert_eq!(format_quantity_as_fraction(1.21), "1.21");
+    }
+}
@@ -0,0 +1,136 @@
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::WeekView;
+
+/// Fetches everything a week view screen needs in one round trip: planned
+/// meals for `[start_date, end_date]` (with each recipe's name and image
+/// inlined), the recipe-derived shopping list for the same range, and
+/// `week_start`'s manual shopping items — replacing what would otherwise be
+/// a meal-plans call, a shopping-list call, a manual-items call, and a
+/// per-recipe detail fetch for every planned meal.
+pub async fn get_week_view(
+    pool: &SqlitePool,
+    week_start: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<WeekView, AppError> {
+    let meal_plans =
+        db::meal_plans::get_meal_plans_with_recipes(pool, start_date, end_date).await?;
+    let shopping_list =
+        db::shopping_list::get_aggregated_shopping_list(pool, start_date, end_date).await?;
+    let manual_items = db::manual_items::list_items_for_week(pool, week_start).await?;
+
+    Ok(WeekView {
+        meal_plans,
+        shopping_list,
+        manual_items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::manual_items::create_manual_item;
+    use crate::db::meal_plans::create_meal_plan;
+    use crate::db::pool::init_db_for_test;
+    use crate::db::recipes::{add_recipe_ingredient, create_recipe};
+    use crate::models::{
+        ManualShoppingItemInput, MealPlanInput, RecipeIngredientExport, RecipeInput,
+    };
+
+    fn sample_recipe_input(name: &str) -> RecipeInput {
+        RecipeInput {
+            name: name.to_string(),
+            description: None,
+            servings: 4,
+            yield_unit: None,
+            prep_time: Some(10),
+            cook_time: Some(15),
+            instructions: vec!["Mix".into(), "Cook".into()],
+            image_path: Some("week-view-pancakes.jpg".to_string()),
+            source_url: None,
+            notes: None,
+            rating_value: None,
+            rating_count: None,
+            difficulty: None,
+            yield_notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn get_week_view_assembles_meal_plans_shopping_list_and_manual_items() {
+        let pool = init_db_for_test().await;
+        let recipe = create_recipe(&pool, sample_recipe_input("Week View Pancakes"))
+            .await
+            .unwrap();
+        add_recipe_ingredient(
+            &pool,
+            &recipe.id,
+            &RecipeIngredientExport {
+                name: "week-view-flour".to_string(),
+                quantity: 2.0,
+                unit: "cup".to_string(),
+                notes: None,
+                sort_order: 0,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        create_meal_plan(
+            &pool,
+            MealPlanInput {
+                recipe_id: recipe.id.clone(),
+                date: "2026-09-07".to_string(),
+                meal_type: "breakfast".to_string(),
+                servings: Some(4),
+            },
+        )
+        .await
+        .unwrap();
+        create_manual_item(
+            &pool,
+            ManualShoppingItemInput {
+                week_start: "2026-09-07".to_string(),
+                name: "week-view-napkins".to_string(),
+                quantity: Some(1.0),
+                unit: Some("pack".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let view = get_week_view(&pool, "2026-09-07", "2026-09-07", "2026-09-13")
+            .await
+            .unwrap();
+
+        assert_eq!(view.meal_plans.len(), 1);
+        assert_eq!(view.meal_plans[0].recipe_name, "Week View Pancakes");
+        assert_eq!(
+            view.meal_plans[0].recipe_image_path,
+            Some("week-view-pancakes.jpg".to_string())
+        );
+        assert_eq!(view.manual_items.len(), 1);
+        assert_eq!(view.manual_items[0].name, "week-view-napkins");
+        assert!(view
+            .shopping_list
+            .iter()
+            .any(|item| item.source_recipe_ids.contains(&recipe.id)));
+    }
+
+    #[tokio::test]
+    async fn get_week_view_returns_empty_sections_for_an_empty_week() {
+        let pool = init_db_for_test().await;
+
+        let view = get_week_view(&pool, "2026-10-05", "2026-10-05", "2026-10-11")
+            .await
+            .unwrap();
+
+        assert!(view.meal_plans.is_empty());
+        assert!(view.shopping_list.is_empty());
+        assert!(view.manual_items.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::{CategoryGroup, Ingredient};
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn autocomplete_ingredients(
+    state: State<'_, AppState>,
+    prefix: String,
+    limit: i64,
+) -> Result<Vec<Ingredient>, String> {
+    db::ingredients::search_ingredients(&state.pool, &prefix, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_categories(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    db::ingredients::get_used_categories(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_ingredients_grouped(
+    state: State<'_, AppState>,
+) -> Result<Vec<CategoryGroup>, String> {
+    db::ingredients::get_ingredients_grouped(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_ingredient_default_unit(
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<Option<String>, String> {
+    db::ingredients::get_default_unit(&state.pool, &name)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn recategorize_items(
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+) -> Result<u64, String> {
+    db::ingredients::recategorize(&state.pool, &from, &to)
+        .await
+        .map_err(Into::into)
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use feast_core::logging::{self, LogConfigSummary};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn validate_log_config(json: String) -> Result<LogConfigSummary, String> {
+    logging::validate_log_config(&json).map_err(|e| e.to_string())
+}
+
+/// Logs a message from the frontend at `level` (falling back to `info` for
+/// an unrecognized level). `data` is formatted with
+/// [`logging::format_frontend_log_data`] before being appended, so a
+/// frontend dumping a large structured payload (e.g. a whole recipe)
+/// can't turn one log line into megabytes. Name-like values in `data` are
+/// redacted down to their length instead of shown verbatim when the app was
+/// started with `redact_content` set in `logging.json`.
+#[tauri::command]
+pub fn log_from_frontend(
+    state: State<'_, AppState>,
+    level: String,
+    message: String,
+    data: BTreeMap<String, Value>,
+) {
+    let data = logging::format_frontend_log_data(&data, state.redact_content);
+    match logging::parse_level(&level) {
+        Some(log::LevelFilter::Error) => log::error!("{message} {data}"),
+        Some(log::LevelFilter::Warn) => log::warn!("{message} {data}"),
+        Some(log::LevelFilter::Debug) => log::debug!("{message} {data}"),
+        Some(log::LevelFilter::Trace) => log::trace!("{message} {data}"),
+        _ => log::info!("{message} {data}"),
+    }
+}
+
+/// Returns the most recent `lines` entries (capped at
+/// [`logging::MAX_LOG_TAIL_LINES`]) from the `feast.log` file in the app's
+/// log directory, optionally keeping only entries at or more severe than
+/// `min_level`, for a debugging view that doesn't require finding the log
+/// file on disk. A log directory that can't be resolved, or a log file
+/// that doesn't exist yet, both come back as an empty vec rather than an
+/// error — there's simply nothing to show yet.
+#[tauri::command]
+pub fn get_recent_logs(
+    app: AppHandle,
+    lines: usize,
+    min_level: Option<String>,
+) -> Result<Vec<Value>, String> {
+    let Ok(log_dir) = app.path().app_log_dir() else {
+        return Ok(Vec::new());
+    };
+    let path = log_dir.join("feast.log");
+    Ok(logging::tail_log_entries(
+        &path,
+        lines,
+        min_level.as_deref(),
+    ))
+}
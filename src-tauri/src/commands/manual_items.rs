@@ -0,0 +1,107 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::{
+    FrequentItem, ListDiff, ListProgress, ManualShoppingItem, ManualShoppingItemInput,
+    ShoppingListSummary,
+};
+use feast_core::utils::dates::validate_ymd;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn clear_checked_manual_items(
+    state: State<'_, AppState>,
+    week_start: String,
+) -> Result<u64, String> {
+    let week_start = validate_ymd(&week_start)?;
+    db::manual_items::delete_checked_items(&state.pool, &week_start)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn add_shopping_item(
+    state: State<'_, AppState>,
+    mut input: ManualShoppingItemInput,
+    merge_duplicates: bool,
+) -> Result<ManualShoppingItem, String> {
+    input.week_start = validate_ymd(&input.week_start)?;
+    db::manual_items::add_shopping_item(&state.pool, input, merge_duplicates)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_frequent_items(
+    state: State<'_, AppState>,
+    limit: i64,
+) -> Result<Vec<FrequentItem>, String> {
+    db::manual_items::get_frequent_items(&state.pool, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_shopping_list_progress(
+    state: State<'_, AppState>,
+    week_start: String,
+) -> Result<ListProgress, String> {
+    let week_start = validate_ymd(&week_start)?;
+    db::manual_items::get_list_progress(&state.pool, &week_start)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_all_shopping_lists(
+    state: State<'_, AppState>,
+) -> Result<Vec<ShoppingListSummary>, String> {
+    db::manual_items::get_all_lists(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn promote_manual_item(
+    state: State<'_, AppState>,
+    id: String,
+    list_id: String,
+) -> Result<ManualShoppingItem, String> {
+    db::manual_items::promote_to_shopping_list(&state.pool, &id, &list_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn toggle_shopping_item(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ManualShoppingItem, String> {
+    db::manual_items::toggle_item_checked(&state.pool, &id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn permanently_delete_shopping_item(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    db::manual_items::hard_delete_shopping_item(&state.pool, &id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn diff_shopping_lists(
+    state: State<'_, AppState>,
+    list_a: String,
+    list_b: String,
+) -> Result<ListDiff, String> {
+    let list_a = validate_ymd(&list_a)?;
+    let list_b = validate_ymd(&list_b)?;
+    db::manual_items::diff_lists(&state.pool, &list_a, &list_b)
+        .await
+        .map_err(Into::into)
+}
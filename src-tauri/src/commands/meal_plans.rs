@@ -0,0 +1,82 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::{DayTimeTotal, MealPlan, MealPlanTemplateEntry, MealPlanWithRecipe};
+use feast_core::utils::dates::validate_ymd;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_recipe_meal_plans(
+    state: State<'_, AppState>,
+    recipe_id: String,
+) -> Result<Vec<MealPlan>, String> {
+    db::meal_plans::get_occurrences_for_recipe(&state.pool, &recipe_id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_meal_plan_time_totals(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<DayTimeTotal>, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    db::meal_plans::get_daily_time_totals(&state.pool, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn defer_meal_plan(
+    state: State<'_, AppState>,
+    id: String,
+    new_date: String,
+) -> Result<MealPlan, String> {
+    let new_date = validate_ymd(&new_date)?;
+    db::meal_plans::defer_meal_plan(&state.pool, &id, &new_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn apply_meal_plan_template(
+    state: State<'_, AppState>,
+    template: Vec<MealPlanTemplateEntry>,
+    week_start: String,
+) -> Result<Vec<MealPlan>, String> {
+    let week_start = validate_ymd(&week_start)?;
+    db::meal_plans::apply_template(&state.pool, template, &week_start)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn search_meal_plans(
+    state: State<'_, AppState>,
+    name: String,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<MealPlanWithRecipe>, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    db::meal_plans::find_plans_by_recipe_name(&state.pool, &name, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn export_week_menu(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    format: String,
+) -> Result<String, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    feast_core::menu_export::export_week_menu(&state.pool, &start_date, &end_date, &format)
+        .await
+        .map_err(Into::into)
+}
@@ -0,0 +1,11 @@
+pub mod ingredients;
+pub mod logging;
+pub mod manual_items;
+pub mod meal_plans;
+pub mod pantry;
+pub mod quick_lists;
+pub mod recipes;
+pub mod shopping_list;
+pub mod shopping_lists;
+pub mod units;
+pub mod week_view;
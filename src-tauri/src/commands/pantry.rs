@@ -0,0 +1,15 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::RestockSuggestion;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_restock_suggestions(
+    state: State<'_, AppState>,
+) -> Result<Vec<RestockSuggestion>, String> {
+    db::pantry::suggest_restock(&state.pool)
+        .await
+        .map_err(Into::into)
+}
@@ -0,0 +1,91 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::{
+    ManualShoppingItem, QuickList, QuickListInput, QuickListItem, QuickListItemInput,
+    QuickListWithItems,
+};
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn create_quick_list(
+    state: State<'_, AppState>,
+    input: QuickListInput,
+    idempotency_key: Option<String>,
+) -> Result<QuickList, String> {
+    if let Some(key) = &idempotency_key {
+        if let db::idempotency::Reservation::AlreadyClaimed(existing_id) =
+            db::idempotency::reserve(&state.pool, key)
+                .await
+                .map_err(Into::<String>::into)?
+        {
+            if let Some(existing) = db::quick_lists::get_quick_list_by_id(&state.pool, &existing_id)
+                .await
+                .map_err(Into::<String>::into)?
+            {
+                return Ok(existing);
+            }
+        }
+    }
+
+    let list = match db::quick_lists::create_quick_list(&state.pool, input).await {
+        Ok(list) => list,
+        Err(err) => {
+            if let Some(key) = &idempotency_key {
+                db::idempotency::release(&state.pool, key).await.ok();
+            }
+            return Err(err.into());
+        }
+    };
+
+    if let Some(key) = &idempotency_key {
+        db::idempotency::record(&state.pool, key, &list.id)
+            .await
+            .map_err(Into::<String>::into)?;
+    }
+
+    Ok(list)
+}
+
+#[tauri::command]
+pub async fn add_quick_list_item(
+    state: State<'_, AppState>,
+    input: QuickListItemInput,
+) -> Result<QuickListItem, String> {
+    db::quick_lists::add_quick_list_item(&state.pool, input)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn duplicate_quick_list(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<QuickListWithItems, String> {
+    db::quick_lists::duplicate_quick_list(&state.pool, &id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn add_quick_list_to_shopping(
+    state: State<'_, AppState>,
+    quick_list_id: String,
+    week_start: String,
+    merge: bool,
+) -> Result<Vec<ManualShoppingItem>, String> {
+    db::quick_lists::add_quick_list_to_shopping(&state.pool, &quick_list_id, &week_start, merge)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_stale_quick_lists(
+    state: State<'_, AppState>,
+    older_than_days: i64,
+) -> Result<Vec<QuickList>, String> {
+    db::quick_lists::get_stale_quick_lists(&state.pool, older_than_days)
+        .await
+        .map_err(Into::into)
+}
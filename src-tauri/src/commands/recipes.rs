@@ -0,0 +1,198 @@
+use tauri::State;
+
+use feast_core::backup;
+use feast_core::db;
+use feast_core::importer;
+use feast_core::importer::ImportCheck;
+use feast_core::models::{Recipe, RecipeBackup, RecipeInput, RecipeQualityIssue};
+use feast_core::parser::recipe::ParsedRecipe;
+use feast_core::recipe_text;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn create_recipe(
+    state: State<'_, AppState>,
+    input: RecipeInput,
+    idempotency_key: Option<String>,
+) -> Result<Recipe, String> {
+    if let Some(key) = &idempotency_key {
+        if let db::idempotency::Reservation::AlreadyClaimed(existing_id) =
+            db::idempotency::reserve(&state.pool, key)
+                .await
+                .map_err(Into::<String>::into)?
+        {
+            if let Some(existing) = db::recipes::get_recipe_by_id(&state.pool, &existing_id)
+                .await
+                .map_err(Into::<String>::into)?
+            {
+                return Ok(existing);
+            }
+        }
+    }
+
+    let recipe = match db::recipes::create_recipe(&state.pool, input).await {
+        Ok(recipe) => recipe,
+        Err(err) => {
+            if let Some(key) = &idempotency_key {
+                db::idempotency::release(&state.pool, key).await.ok();
+            }
+            return Err(err.into());
+        }
+    };
+
+    if let Some(key) = &idempotency_key {
+        db::idempotency::record(&state.pool, key, &recipe.id)
+            .await
+            .map_err(Into::<String>::into)?;
+    }
+
+    Ok(recipe)
+}
+
+#[tauri::command]
+pub async fn export_all_recipes(state: State<'_, AppState>) -> Result<String, String> {
+    let bundle = backup::export_all_recipes(&state.pool)
+        .await
+        .map_err(Into::<String>::into)?;
+    serde_json::to_string(&bundle).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_all_recipes(state: State<'_, AppState>, json: String) -> Result<usize, String> {
+    let bundle: RecipeBackup = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    backup::import_all_recipes(&state.pool, bundle)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn find_incomplete_recipes(
+    state: State<'_, AppState>,
+) -> Result<Vec<RecipeQualityIssue>, String> {
+    db::recipes::find_incomplete_recipes(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_imported_recipes(state: State<'_, AppState>) -> Result<Vec<Recipe>, String> {
+    db::recipes::get_imported_recipes(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_manual_recipes(state: State<'_, AppState>) -> Result<Vec<Recipe>, String> {
+    db::recipes::get_manual_recipes(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_recipes_sorted_by_rating(
+    state: State<'_, AppState>,
+    limit: i64,
+) -> Result<Vec<Recipe>, String> {
+    db::recipes::get_recipes_sorted_by_rating(&state.pool, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_recipe_rating(
+    state: State<'_, AppState>,
+    id: String,
+    rating: Option<i64>,
+) -> Result<Recipe, String> {
+    db::recipes::set_user_rating(&state.pool, &id, rating)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_top_rated_recipes(
+    state: State<'_, AppState>,
+    limit: i64,
+) -> Result<Vec<Recipe>, String> {
+    db::recipes::get_top_rated_recipes(&state.pool, limit)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_recipe_by_source_url(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<Option<Recipe>, String> {
+    db::recipes::get_recipe_by_source_url(&state.pool, &url)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, AppState>,
+    old: String,
+    new: String,
+) -> Result<(), String> {
+    db::recipes::rename_tag(&state.pool, &old, &new)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_tag_histogram(state: State<'_, AppState>) -> Result<Vec<(String, i64)>, String> {
+    db::recipes::get_tag_histogram(&state.pool)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn clear_recipe_image(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    db::recipes::clear_image(&state.pool, &id)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn import_recipe_from_url(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<ParsedRecipe, String> {
+    importer::import_recipe_from_url(&state.pool, &url)
+        .await
+        .map_err(|err| importer::import_error_response(&err))
+}
+
+#[tauri::command]
+pub async fn can_import_url(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<ImportCheck, String> {
+    importer::check_import_url(&state.pool, &url)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn scaled_recipe_text(
+    state: State<'_, AppState>,
+    id: String,
+    target_servings: i64,
+) -> Result<String, String> {
+    recipe_text::scaled_recipe_text(&state.pool, &id, target_servings)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn import_recipe_from_jsonld(
+    state: State<'_, AppState>,
+    jsonld: String,
+    source_url: Option<String>,
+) -> Result<Recipe, String> {
+    importer::import_recipe_from_jsonld(&state.pool, &jsonld, source_url)
+        .await
+        .map_err(Into::into)
+}
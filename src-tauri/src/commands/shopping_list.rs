@@ -0,0 +1,87 @@
+use tauri::State;
+
+use feast_core::correlation::{ensure_correlation_id, with_correlation_id};
+use feast_core::db;
+use feast_core::models::{AggregatedShoppingItem, SharedIngredient};
+use feast_core::utils::dates::validate_ymd;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_aggregated_shopping_list(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<AggregatedShoppingItem>, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    let cid = ensure_correlation_id(None);
+    with_correlation_id(cid.clone(), async move {
+        log::debug!("[cid:{cid}] get_aggregated_shopping_list");
+        db::shopping_list::get_aggregated_shopping_list(&state.pool, &start_date, &end_date)
+            .await
+            .map_err(Into::into)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn export_aggregated_csv(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<String, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    feast_core::export::export_aggregated_shopping_list_csv(&state.pool, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn export_aggregated_text(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<String, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    feast_core::export::export_aggregated_shopping_list_text(&state.pool, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_shared_ingredients(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<SharedIngredient>, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    db::shopping_list::get_shared_ingredients(&state.pool, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn get_week_shopping_gap(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    week_start: String,
+    subtract_pantry: bool,
+) -> Result<Vec<AggregatedShoppingItem>, String> {
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    let week_start = validate_ymd(&week_start)?;
+    db::shopping_list::get_week_shopping_gap(
+        &state.pool,
+        &start_date,
+        &end_date,
+        &week_start,
+        subtract_pantry,
+    )
+    .await
+    .map_err(Into::into)
+}
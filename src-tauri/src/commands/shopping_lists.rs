@@ -0,0 +1,18 @@
+use tauri::State;
+
+use feast_core::db;
+use feast_core::models::ShoppingList;
+use feast_core::utils::dates::validate_ymd;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_or_create_week_list(
+    state: State<'_, AppState>,
+    week_start: String,
+) -> Result<ShoppingList, String> {
+    let week_start = validate_ymd(&week_start)?;
+    db::shopping_lists::get_or_create_week_list(&state.pool, &week_start)
+        .await
+        .map_err(Into::into)
+}
@@ -0,0 +1,6 @@
+use feast_core::utils::units;
+
+#[tauri::command]
+pub fn parse_measurement(input: String) -> (f64, String, Option<(f64, String)>) {
+    units::parse_measurement(&input)
+}
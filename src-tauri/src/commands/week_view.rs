@@ -0,0 +1,22 @@
+use tauri::State;
+
+use feast_core::models::WeekView;
+use feast_core::utils::dates::validate_ymd;
+use feast_core::week_view;
+
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_week_view(
+    state: State<'_, AppState>,
+    week_start: String,
+    start_date: String,
+    end_date: String,
+) -> Result<WeekView, String> {
+    let week_start = validate_ymd(&week_start)?;
+    let start_date = validate_ymd(&start_date)?;
+    let end_date = validate_ymd(&end_date)?;
+    week_view::get_week_view(&state.pool, &week_start, &start_date, &end_date)
+        .await
+        .map_err(Into::into)
+}
@@ -0,0 +1,103 @@
+mod commands;
+mod logging;
+mod state;
+
+use feast_core::db::pool::{close_db, init_db_with_recovery};
+use state::AppState;
+use tauri::Manager;
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let log_config = logging::load_log_config();
+
+    tauri::Builder::default()
+        .plugin(logging::build_log_plugin(&log_config, None).build())
+        .setup(|app| {
+            let pool = tauri::async_runtime::block_on(async {
+                match init_db_with_recovery("sqlite:feast.db").await {
+                    Ok(pool) => pool,
+                    Err(err) => {
+                        // `init_db_with_recovery` already attempted a backup
+                        // + recreate; reaching here means even that failed,
+                        // so there is genuinely no usable database to run
+                        // with.
+                        log::error!("database unrecoverable, exiting: {err}");
+                        std::process::exit(1);
+                    }
+                }
+            });
+            app.manage(AppState {
+                pool,
+                redact_content: log_config.redact_content,
+            });
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::ingredients::autocomplete_ingredients,
+            commands::ingredients::get_categories,
+            commands::ingredients::get_ingredient_default_unit,
+            commands::ingredients::get_ingredients_grouped,
+            commands::ingredients::recategorize_items,
+            commands::logging::validate_log_config,
+            commands::logging::log_from_frontend,
+            commands::logging::get_recent_logs,
+            commands::manual_items::clear_checked_manual_items,
+            commands::manual_items::add_shopping_item,
+            commands::manual_items::diff_shopping_lists,
+            commands::manual_items::get_all_shopping_lists,
+            commands::manual_items::get_frequent_items,
+            commands::manual_items::get_shopping_list_progress,
+            commands::manual_items::permanently_delete_shopping_item,
+            commands::manual_items::promote_manual_item,
+            commands::manual_items::toggle_shopping_item,
+            commands::meal_plans::get_recipe_meal_plans,
+            commands::meal_plans::get_meal_plan_time_totals,
+            commands::meal_plans::defer_meal_plan,
+            commands::meal_plans::apply_meal_plan_template,
+            commands::meal_plans::search_meal_plans,
+            commands::meal_plans::export_week_menu,
+            commands::pantry::get_restock_suggestions,
+            commands::quick_lists::create_quick_list,
+            commands::quick_lists::add_quick_list_item,
+            commands::quick_lists::duplicate_quick_list,
+            commands::quick_lists::add_quick_list_to_shopping,
+            commands::quick_lists::get_stale_quick_lists,
+            commands::recipes::can_import_url,
+            commands::recipes::export_all_recipes,
+            commands::recipes::import_all_recipes,
+            commands::recipes::find_incomplete_recipes,
+            commands::recipes::get_imported_recipes,
+            commands::recipes::get_manual_recipes,
+            commands::recipes::create_recipe,
+            commands::recipes::get_recipe_by_source_url,
+            commands::recipes::get_recipes_sorted_by_rating,
+            commands::recipes::set_recipe_rating,
+            commands::recipes::get_top_rated_recipes,
+            commands::recipes::import_recipe_from_jsonld,
+            commands::recipes::scaled_recipe_text,
+            commands::recipes::import_recipe_from_url,
+            commands::recipes::rename_tag,
+            commands::recipes::get_tag_histogram,
+            commands::recipes::clear_recipe_image,
+            commands::shopping_list::export_aggregated_csv,
+            commands::shopping_list::export_aggregated_text,
+            commands::shopping_list::get_aggregated_shopping_list,
+            commands::shopping_list::get_shared_ingredients,
+            commands::shopping_list::get_week_shopping_gap,
+            commands::shopping_lists::get_or_create_week_list,
+            commands::units::parse_measurement,
+            commands::week_view::get_week_view,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building feast")
+        .run(|app_handle, event| {
+            // Closes the pool and checkpoints its WAL on exit so a killed
+            // process doesn't leave buffered writes uncheckpointed; logs are
+            // flushed right after so the shutdown itself gets recorded.
+            if let tauri::RunEvent::Exit = event {
+                let state = app_handle.state::<AppState>();
+                tauri::async_runtime::block_on(close_db(&state.pool));
+                log::logger().flush();
+            }
+        });
+}
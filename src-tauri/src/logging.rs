@@ -0,0 +1,86 @@
+use feast_core::logging::{self, LogConfig};
+use tauri_plugin_log::{Target, TargetKind};
+
+/// Builds the log target list for `config`. Defaults to stdout plus the
+/// webview, unless `config.include_webview` is `false` — set by
+/// headless/CLI runs and integration tests that initialize logging without
+/// a running webview, where emitting to a nonexistent one can error or
+/// silently drop. `targets_override`, when given, replaces the default
+/// composition entirely; it exists so callers (tests, a future CLI mode)
+/// can assert against or substitute an explicit target list.
+pub fn build_log_targets(config: &LogConfig, targets_override: Option<Vec<Target>>) -> Vec<Target> {
+    if let Some(targets) = targets_override {
+        return targets;
+    }
+
+    let mut targets = vec![Target::new(TargetKind::Stdout)];
+    if config.include_webview {
+        targets.push(Target::new(TargetKind::Webview));
+    }
+    targets
+}
+
+/// Builds the `tauri-plugin-log` plugin from `config`: the default level,
+/// any per-module overrides, and the target list from
+/// [`build_log_targets`]. Levels are expected to have already been checked
+/// by [`feast_core::logging::validate_log_config`]; anything that slips
+/// through unparsed here falls back to [`log::LevelFilter::Info`] rather
+/// than panicking at startup.
+pub fn build_log_plugin(
+    config: &LogConfig,
+    targets_override: Option<Vec<Target>>,
+) -> tauri_plugin_log::Builder {
+    let mut builder = tauri_plugin_log::Builder::new()
+        .targets(build_log_targets(config, targets_override))
+        .level(logging::parse_level(&config.default_level).unwrap_or(log::LevelFilter::Info));
+
+    for (module, level) in &config.modules {
+        if let Some(level) = logging::parse_level(level) {
+            builder = builder.level_for(module.clone(), level);
+        }
+    }
+
+    builder
+}
+
+/// Reads `logging.json` from the working directory via [`LogConfig::load`],
+/// falling back to [`LogConfig::default`] when the file is missing,
+/// unreadable, malformed, or has an unparseable level. Failures are only
+/// surfaced as an `eprintln`, since this runs before the logging plugin
+/// itself is available — a settings UI should steer users to
+/// `validate_log_config` instead of hitting this path.
+pub fn load_log_config() -> LogConfig {
+    LogConfig::load(std::path::Path::new("logging.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn config(include_webview: bool) -> LogConfig {
+        LogConfig {
+            default_level: "info".to_string(),
+            modules: BTreeMap::new(),
+            include_webview,
+        }
+    }
+
+    #[test]
+    fn includes_webview_target_by_default() {
+        let targets = build_log_targets(&config(true), None);
+        assert_eq!(targets.len(), 2);
+    }
+
+    #[test]
+    fn omits_webview_target_when_disabled() {
+        let targets = build_log_targets(&config(false), None);
+        assert_eq!(targets.len(), 1);
+    }
+
+    #[test]
+    fn override_replaces_the_default_composition_entirely() {
+        let targets = build_log_targets(&config(true), Some(vec![Target::new(TargetKind::Stdout)]));
+        assert_eq!(targets.len(), 1);
+    }
+}
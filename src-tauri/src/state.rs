@@ -0,0 +1,12 @@
+use sqlx::SqlitePool;
+
+/// Tauri-managed application state, registered via `app.manage(...)` in
+/// `setup`. Commands pull the pool out of `State<AppState>` instead of
+/// reaching for a process-wide global, so the app (and its tests) can run
+/// against more than one database at a time.
+pub struct AppState {
+    pub pool: SqlitePool,
+    /// Mirrors `LogConfig::redact_content` at startup, so logging command
+    /// handlers don't need to re-read `logging.json` on every call.
+    pub redact_content: bool,
+}